@@ -0,0 +1,72 @@
+use syn::{
+    visit::{self, Visit},
+    Ident, TypePath,
+};
+
+// Collects what a field type needs bounded for the builder to be Clone or
+// Debug, the same way the debug crate infers its Debug bounds: every type
+// parameter used directly (`T`, `Vec<T>`, `(K, V)`) and every associated type
+// of one (`T::Item`, `<T as Trait>::Item`).
+//
+// PhantomData, fn pointers and raw pointers are Clone and Debug whatever the
+// parameter is, and trait objects can't be bounded through the parameter at
+// all, so parameters inside them are left out.
+pub struct GenericParamVisiter {
+    pub generic_type_arr: Vec<String>,
+    pub generic_param_arr: Vec<Ident>,
+    pub associated_type_arr: Vec<TypePath>,
+}
+
+impl GenericParamVisiter {
+    fn push_associated_type(&mut self, i: &TypePath) {
+        if !self.associated_type_arr.contains(i) {
+            self.associated_type_arr.push(i.clone());
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for GenericParamVisiter {
+    fn visit_type_path(&mut self, i: &'ast TypePath) {
+        // <T as Trait>::Item
+        if let Some(ref qself) = i.qself {
+            if let syn::Type::Path(ref qself_path) = *qself.ty {
+                if let Some(first) = qself_path.path.segments.first() {
+                    if self.generic_type_arr.contains(&first.ident.to_string()) {
+                        self.push_associated_type(i);
+                    }
+                }
+            }
+            return;
+        }
+
+        if let Some(last) = i.path.segments.last() {
+            if last.ident == "PhantomData" {
+                return;
+            }
+        }
+
+        if let Some(first) = i.path.segments.first() {
+            if self.generic_type_arr.contains(&first.ident.to_string()) {
+                if i.path.segments.len() == 1 {
+                    if !self.generic_param_arr.contains(&first.ident) {
+                        self.generic_param_arr.push(first.ident.clone());
+                    }
+                } else {
+                    // T::Item
+                    self.push_associated_type(i);
+                }
+                return;
+            }
+        }
+
+        visit::visit_type_path(self, i);
+    }
+
+    fn visit_type_bare_fn(&mut self, _: &'ast syn::TypeBareFn) {}
+
+    fn visit_type_ptr(&mut self, _: &'ast syn::TypePtr) {}
+
+    fn visit_type_trait_object(&mut self, _: &'ast syn::TypeTraitObject) {}
+
+    fn visit_type_impl_trait(&mut self, _: &'ast syn::TypeImplTrait) {}
+}
@@ -1,6 +1,11 @@
+mod generic_param_visiter;
+
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, DataStruct, DeriveInput, Field, Fields, FieldsNamed, Ident};
+use syn::{
+    parse_macro_input, parse_quote, visit::Visit, DataStruct, DeriveInput, Field, Fields,
+    FieldsNamed, Ident,
+};
 
 #[proc_macro_derive(Builder, attributes(builder))]
 pub fn derive(input: TokenStream) -> TokenStream {
@@ -26,7 +31,9 @@ enum FieldPathSeg {
 }
 
 fn do_extend(ast: syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
-    let DeriveInput { ident, .. } = &ast;
+    let DeriveInput {
+        ident, generics, ..
+    } = &ast;
 
     // eprintln!("{:#?}", &ast);
 
@@ -37,24 +44,30 @@ fn do_extend(ast: syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
 
     let builder_ident = Ident::new(&format!("{}Builder", ident), ident.span());
 
-    let struct_builder = struct_builder(&builder_ident, &struct_fields)?;
+    let struct_builder = struct_builder(&builder_ident, generics, &struct_fields)?;
+
+    let struct_builder_impl = struct_builder_impl(ident, &builder_ident, generics, &struct_fields)?;
 
-    let struct_builder_impl = struct_builder_impl(&ident, &builder_ident, &struct_fields)?;
+    let struct_builder_trait_impl =
+        struct_builder_trait_impl(&builder_ident, generics, &struct_fields)?;
 
-    let struct_impl = struct_impl(&ident, &builder_ident, &struct_fields)?;
+    let struct_impl = struct_impl(ident, &builder_ident, generics, &struct_fields)?;
 
     Ok(quote!(
         #struct_builder
 
         #struct_builder_impl
 
+        #struct_builder_trait_impl
+
         #struct_impl
     ))
 }
 
 fn struct_builder(
     builder_ident: &Ident,
-    struct_fields: &Vec<FieldInfo>,
+    generics: &syn::Generics,
+    struct_fields: &[FieldInfo],
 ) -> syn::Result<proc_macro2::TokenStream> {
     let mut inner = proc_macro2::TokenStream::new();
     for f in struct_fields.iter() {
@@ -75,8 +88,10 @@ fn struct_builder(
         }
     }
 
+    let where_clause = &generics.where_clause;
+
     Ok(quote!(
-    pub struct #builder_ident {
+    pub struct #builder_ident #generics #where_clause {
         #inner
     }
     ))
@@ -85,7 +100,8 @@ fn struct_builder(
 fn struct_builder_impl(
     ident: &Ident,
     builder_ident: &Ident,
-    struct_fields: &Vec<FieldInfo>,
+    generics: &syn::Generics,
+    struct_fields: &[FieldInfo],
 ) -> syn::Result<proc_macro2::TokenStream> {
     let mut builder_impls = proc_macro2::TokenStream::new();
 
@@ -177,24 +193,26 @@ fn struct_builder_impl(
         });
     ));
 
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
     builder_impls.extend(quote!(
-    fn build(&mut self) -> std::result::Result<#ident, std::boxed::Box<dyn std::error::Error>> {
+    fn build(&mut self) -> std::result::Result<#ident #ty_generics, std::boxed::Box<dyn std::error::Error>> {
         #build_inner
     }
     ));
 
+    let new_impl = builder_new_impl(struct_fields);
+
     Ok(quote!(
-    impl #builder_ident {
+    impl #impl_generics #builder_ident #ty_generics #where_clause {
+        #new_impl
+
         #builder_impls
     }
     ))
 }
 
-fn struct_impl(
-    ident: &Ident,
-    builder_ident: &Ident,
-    struct_fields: &Vec<FieldInfo>,
-) -> syn::Result<proc_macro2::TokenStream> {
+fn builder_new_impl(struct_fields: &[FieldInfo]) -> proc_macro2::TokenStream {
     let mut inner = proc_macro2::TokenStream::new();
 
     for f in struct_fields.iter() {
@@ -206,22 +224,214 @@ fn struct_impl(
 
         if field_path_seg == &FieldPathSeg::Vec {
             inner.extend(quote!(
-                #ident: Some(vec![]),
+                #ident: std::option::Option::Some(std::vec::Vec::new()),
             ));
         } else {
             inner.extend(quote!(
-                #ident: None,
+                #ident: std::option::Option::None,
             ));
         }
     }
 
+    quote!(
+    pub fn new() -> Self {
+        Self {
+            #inner
+        }
+    })
+}
+
+// Default is always available, Clone and Debug only when every field type
+// implements the trait, see add_inferred_bounds.
+fn struct_builder_trait_impl(
+    builder_ident: &Ident,
+    generics: &syn::Generics,
+    struct_fields: &[FieldInfo],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut clone_generics = generics.clone();
+    let mut debug_generics = generics.clone();
+    add_inferred_bounds(
+        &mut clone_generics,
+        struct_fields,
+        &parse_quote!(std::clone::Clone),
+    );
+    add_inferred_bounds(
+        &mut debug_generics,
+        struct_fields,
+        &parse_quote!(std::fmt::Debug),
+    );
+
+    let mut clone_inner = proc_macro2::TokenStream::new();
+    let mut debug_inner = proc_macro2::TokenStream::new();
+
+    for f in struct_fields.iter() {
+        let FieldInfo { ident, .. } = f;
+        let ident_str = ident.to_string();
+
+        clone_inner.extend(quote!(
+            #ident: std::clone::Clone::clone(&self.#ident),
+        ));
+        debug_inner.extend(quote!(
+            .field(#ident_str, &self.#ident)
+        ));
+    }
+
+    let builder_ident_str = builder_ident.to_string();
+    let (clone_impl_generics, _, clone_where) = clone_generics.split_for_impl();
+    let (debug_impl_generics, _, debug_where) = debug_generics.split_for_impl();
+
     Ok(quote!(
-    impl #ident {
-        fn builder() -> #builder_ident{
-            #builder_ident {
-                #inner
+    impl #impl_generics std::default::Default for #builder_ident #ty_generics #where_clause {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl #clone_impl_generics std::clone::Clone for #builder_ident #ty_generics #clone_where {
+        fn clone(&self) -> Self {
+            Self {
+                #clone_inner
+            }
+        }
+    }
+
+    impl #debug_impl_generics std::fmt::Debug for #builder_ident #ty_generics #debug_where {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct(#builder_ident_str)
+                #debug_inner
+                .finish()
+        }
+    }
+    ))
+}
+
+// Bounds the type parameters and associated types the field types use, like
+// `K: Clone` for `key: K`, so that the builder is Clone whenever the struct's
+// parameters allow it.
+//
+// A field type that uses no parameter, `String` or `NotClone`, is bounded as
+// a whole instead. The `for<'__builder>` makes such a predicate non-trivial,
+// so a field type without the impl disables the impl instead of failing to
+// compile.
+fn add_inferred_bounds(
+    generics: &mut syn::Generics,
+    struct_fields: &[FieldInfo],
+    trait_path: &syn::Path,
+) {
+    let generic_type_arr = generics
+        .type_params()
+        .map(|t| t.ident.to_string())
+        .collect::<Vec<_>>();
+
+    let mut generic_param_arr: Vec<Ident> = vec![];
+    let mut associated_type_arr: Vec<syn::TypePath> = vec![];
+    let mut other_ty_arr: Vec<&syn::Type> = vec![];
+    for f in struct_fields.iter() {
+        let mut generic_param_visiter = generic_param_visiter::GenericParamVisiter {
+            generic_type_arr: generic_type_arr.clone(),
+            generic_param_arr: vec![],
+            associated_type_arr: vec![],
+        };
+        generic_param_visiter.visit_type(&f.ty);
+
+        if generic_param_visiter.generic_param_arr.is_empty()
+            && generic_param_visiter.associated_type_arr.is_empty()
+        {
+            if !other_ty_arr.contains(&&f.ty) {
+                other_ty_arr.push(&f.ty);
+            }
+            continue;
+        }
+
+        for param in generic_param_visiter.generic_param_arr {
+            if !generic_param_arr.contains(&param) {
+                generic_param_arr.push(param);
             }
         }
+        for associated_type in generic_param_visiter.associated_type_arr {
+            if !associated_type_arr.contains(&associated_type) {
+                associated_type_arr.push(associated_type);
+            }
+        }
+    }
+
+    for g in generics.params.iter_mut() {
+        if let syn::GenericParam::Type(t) = g {
+            if generic_param_arr.contains(&t.ident) {
+                t.bounds.push(parse_quote!(#trait_path));
+            }
+        }
+    }
+
+    let where_clause = generics.make_where_clause();
+    for associated_type in associated_type_arr {
+        where_clause
+            .predicates
+            .push(parse_quote!(#associated_type: #trait_path));
+    }
+    for ty in other_ty_arr {
+        where_clause
+            .predicates
+            .push(parse_quote!(for<'__builder> #ty: #trait_path));
+    }
+}
+
+fn struct_impl(
+    ident: &Ident,
+    builder_ident: &Ident,
+    generics: &syn::Generics,
+    struct_fields: &[FieldInfo],
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // the local builder, resolved at the macro's own definition site so that
+    // a field or argument named `__builder` can't shadow it
+    let builder_local = Ident::new("__builder", proc_macro2::Span::mixed_site());
+
+    // try_new(executable, ...) takes only the fields build() would complain
+    // about, Option and Vec fields are left at their defaults.
+    let mut try_new_args = proc_macro2::TokenStream::new();
+    let mut try_new_inner = proc_macro2::TokenStream::new();
+    for f in struct_fields.iter() {
+        let FieldInfo {
+            ident,
+            ty,
+            field_path_seg,
+            ..
+        } = f;
+
+        if field_path_seg == &FieldPathSeg::Normal {
+            try_new_args.extend(quote!(#ident: #ty,));
+            try_new_inner.extend(quote!(
+                #builder_local.#ident(#ident);
+            ));
+        }
+    }
+
+    // The closure's type parameter is `__F` so that it can't clash with the
+    // struct's own, and the bound names its lifetime because a struct with a
+    // lifetime parameter leaves elision with more than one input lifetime.
+    Ok(quote!(
+    impl #impl_generics #ident #ty_generics #where_clause {
+        fn builder() -> #builder_ident #ty_generics {
+            #builder_ident::new()
+        }
+
+        pub fn build_with<__F>(f: __F) -> std::result::Result<Self, std::boxed::Box<dyn std::error::Error>>
+        where
+            __F: for<'__b> std::ops::FnOnce(&'__b mut #builder_ident #ty_generics) -> &'__b mut #builder_ident #ty_generics,
+        {
+            let mut #builder_local = #builder_ident::new();
+            f(&mut #builder_local).build()
+        }
+
+        pub fn try_new(#try_new_args) -> std::result::Result<Self, std::boxed::Box<dyn std::error::Error>> {
+            let mut #builder_local = #builder_ident::new();
+            #try_new_inner
+            #builder_local.build()
+        }
     }))
 }
 
@@ -234,7 +444,7 @@ fn get_struct_fields(ast: &syn::DeriveInput) -> syn::Result<Vec<FieldInfo>> {
     {
         named
             .iter()
-            .map(|f| get_real_field_info(f))
+            .map(get_real_field_info)
             .collect::<syn::Result<Vec<_>>>()
     } else {
         syn::Result::Err(syn::Error::new_spanned(
@@ -280,7 +490,9 @@ fn get_real_field_info(f: &Field) -> syn::Result<FieldInfo> {
                         let value = meta.value()?;
                         // this parses `"EarlGrey"`
                         let s: syn::LitStr = value.parse()?;
-                        each = Some(Ident::new(&s.value(), s.span()));
+                        // a name like "1arg" or "fn" is reported on the
+                        // string instead of panicking in Ident::new
+                        each = Some(s.parse::<Ident>()?);
                         Ok(())
                     } else {
                         if let syn::Meta::List(ref list) = attr.meta {
@@ -298,9 +510,7 @@ fn get_real_field_info(f: &Field) -> syn::Result<FieldInfo> {
                     }
                 });
 
-                if let Err(err) = res {
-                    return Err(err);
-                }
+                res?;
             }
         }
 
@@ -314,3 +524,6 @@ fn get_real_field_info(f: &Field) -> syn::Result<FieldInfo> {
         Err(syn::Error::new_spanned(f, "no ident"))
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,80 @@
+// Snapshots of the code generated by do_extend. Every tests/expand/NAME.rs
+// input is expanded and compared against tests/expand/NAME.expanded.rs.
+//
+// A missing snapshot is written out and reported as a failure so that it gets
+// reviewed. To accept intended changes to the generated code, run:
+//
+//     $ BUILDER_SNAPSHOT=overwrite cargo test
+
+use std::{fs, path::Path};
+
+use syn::{punctuated::Punctuated, Token};
+
+use crate::do_extend;
+
+#[test]
+fn expand() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/expand");
+    let overwrite = std::env::var_os("BUILDER_SNAPSHOT").is_some_and(|v| v == "overwrite");
+
+    let mut input_arr = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            let name = path.file_name().unwrap().to_string_lossy();
+            name.ends_with(".rs") && !name.ends_with(".expanded.rs")
+        })
+        .collect::<Vec<_>>();
+    input_arr.sort();
+    assert!(!input_arr.is_empty(), "no inputs in {}", dir.display());
+
+    let mut failure_arr = vec![];
+    for input in input_arr {
+        let actual = expand_file(&input);
+        let snapshot = input.with_extension("expanded.rs");
+
+        if overwrite {
+            fs::write(&snapshot, &actual).unwrap();
+        } else if let Ok(expected) = fs::read_to_string(&snapshot) {
+            if expected != actual {
+                failure_arr.push(format!(
+                    "{} changed, actual expansion:\n{}",
+                    snapshot.display(),
+                    actual
+                ));
+            }
+        } else {
+            fs::write(&snapshot, &actual).unwrap();
+            failure_arr.push(format!("{} was missing, wrote it", snapshot.display()));
+        }
+    }
+
+    assert!(failure_arr.is_empty(), "{}", failure_arr.join("\n\n"));
+}
+
+fn expand_file(input: &Path) -> String {
+    let source = fs::read_to_string(input).unwrap();
+    let file = syn::parse_file(&source).unwrap();
+
+    let mut expanded = proc_macro2::TokenStream::new();
+    for item in file.items {
+        if let syn::Item::Struct(item) = item {
+            if is_derive_builder(&item.attrs) {
+                expanded.extend(do_extend(item.into()).unwrap_or_else(|e| e.to_compile_error()));
+            }
+        }
+    }
+
+    prettyplease::unparse(&syn::parse2(expanded).unwrap())
+}
+
+fn is_derive_builder(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("derive")
+            && attr
+                .parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .unwrap()
+                .iter()
+                .any(|path| path.is_ident("Builder"))
+    })
+}
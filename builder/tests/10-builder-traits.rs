@@ -0,0 +1,88 @@
+// Generate `CommandBuilder::new()` and `impl Default for CommandBuilder` so
+// the builder can be created without going through `Command::builder()`, for
+// example when it is stored in another struct or constructed by generic code.
+//
+// Also implement Clone and Debug for the builder, but only when every field
+// type supports it. A field whose type is not Clone must not stop the rest of
+// the builder from being generated; it only means the builder is not Clone.
+//
+// Type parameters of the struct carry over to the builder. The bounds go on
+// the parameters the fields actually use, so a builder holding only an
+// `I::Item` is Clone even when the iterator `I` itself is not.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+#[derive(Builder)]
+pub struct Pair<K, V> {
+    key: K,
+    value: Option<V>,
+}
+
+pub struct NotClone;
+
+#[derive(Builder)]
+pub struct Resource {
+    handle: NotClone,
+}
+
+#[derive(Builder)]
+pub struct Next<I: Iterator> {
+    item: I::Item,
+    rest: Vec<I::Item>,
+}
+
+pub struct Counter;
+
+impl Iterator for Counter {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        None
+    }
+}
+
+#[derive(Default)]
+pub struct Holder {
+    command: CommandBuilder,
+}
+
+fn make<T: Default>() -> T {
+    T::default()
+}
+
+fn main() {
+    let mut holder = Holder::default();
+    holder.command.executable("cargo".to_owned()).arg("build".to_owned());
+
+    let mut copy = holder.command.clone();
+    copy.current_dir("..".to_owned());
+
+    let debug = format!("{:?}", holder.command);
+    let expected = r#"CommandBuilder { executable: Some("cargo"), args: Some(["build"]), current_dir: None }"#;
+    assert_eq!(debug, expected);
+
+    let command = copy.build().unwrap();
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.current_dir.as_deref(), Some(".."));
+
+    let mut pair: PairBuilder<&str, u8> = make();
+    let pair = pair.key("a").value(1).build().unwrap();
+    assert_eq!(pair.key, "a");
+    assert_eq!(pair.value, Some(1));
+
+    let resource = ResourceBuilder::new().handle(NotClone).build().unwrap();
+    let NotClone = resource.handle;
+
+    let mut next = NextBuilder::<Counter>::new();
+    next.item(1).rest(vec![2, 3]);
+    let next = next.clone().build().unwrap();
+    assert_eq!(next.rest, vec![2, 3]);
+}
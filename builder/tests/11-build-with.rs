@@ -0,0 +1,76 @@
+// Simple call sites shouldn't need to name the builder at all.
+//
+// `Command::build_with` hands a fresh builder to a closure and builds
+// whatever the closure configured:
+//
+//     let command = Command::build_with(|b| b.executable("cargo".to_owned()))?;
+//
+// `Command::try_new` is a positional constructor taking one argument per
+// required field, in declaration order. Option and Vec fields are not
+// parameters and keep their empty defaults.
+//
+//     let command = Command::try_new("cargo".to_owned(), 1)?;
+//
+// Both work on structs with lifetime and type parameters, whatever the
+// parameters and fields are called.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+    priority: u8,
+}
+
+#[derive(Builder)]
+pub struct Borrowed<'a> {
+    name: &'a str,
+}
+
+#[derive(Builder)]
+pub struct Gen<F> {
+    f: F,
+}
+
+#[derive(Builder)]
+pub struct Shadowed {
+    __builder: u8,
+}
+
+fn main() {
+    let command = Command::build_with(|b| {
+        b.executable("cargo".to_owned())
+            .arg("build".to_owned())
+            .priority(3)
+    })
+    .unwrap();
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.priority, 3);
+
+    let missing = Command::build_with(|b| b.executable("cargo".to_owned()));
+    assert!(missing.is_err());
+
+    let command = Command::try_new("rustc".to_owned(), 1).unwrap();
+    assert_eq!(command.executable, "rustc");
+    assert!(command.args.is_empty());
+    assert!(command.current_dir.is_none());
+    assert_eq!(command.priority, 1);
+
+    let owner = "cargo".to_owned();
+    let borrowed = Borrowed::build_with(|b| b.name(&owner)).unwrap();
+    assert_eq!(borrowed.name, "cargo");
+
+    let gen = Gen::build_with(|b| b.f(7u8)).unwrap();
+    assert_eq!(gen.f, 7);
+    let gen = Gen::try_new("seven").unwrap();
+    assert_eq!(gen.f, "seven");
+
+    let shadowed = Shadowed::try_new(5).unwrap();
+    assert_eq!(shadowed.__builder, 5);
+    let shadowed = Shadowed::build_with(|b| b.__builder(6)).unwrap();
+    assert_eq!(shadowed.__builder, 6);
+}
@@ -0,0 +1,14 @@
+// The builder only makes sense for structs with named fields. Enums, unions
+// and tuple structs are rejected with an error pointing at the type name.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Point(i32, i32);
+
+#[derive(Builder)]
+pub enum Shape {
+    Circle,
+}
+
+fn main() {}
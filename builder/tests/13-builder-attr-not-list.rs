@@ -0,0 +1,20 @@
+// The field attribute is always written as a list, `#[builder(each = "...")]`.
+// The bare and name-value forms are rejected.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder]
+    args: Vec<String>,
+}
+
+#[derive(Builder)]
+pub struct Process {
+    executable: String,
+    #[builder = "arg"]
+    args: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+// The name given to `each` must be a string literal.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = arg)]
+    args: Vec<String>,
+}
+
+fn main() {}
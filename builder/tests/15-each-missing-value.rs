@@ -0,0 +1,13 @@
+// `each` on its own, without `= "..."`, is an error rather than a method
+// named after nothing.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each)]
+    args: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+// The string given to `each` becomes a method name, so it has to be a valid
+// identifier, and not a keyword. The error points at the string literal.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "1arg")]
+    args: Vec<String>,
+}
+
+#[derive(Builder)]
+pub struct Script {
+    #[builder(each = "fn")]
+    lines: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,102 @@
+pub struct CommandBuilder {
+    executable: std::option::Option<String>,
+    args: std::option::Option<Vec<String>>,
+    current_dir: std::option::Option<String>,
+}
+impl CommandBuilder {
+    pub fn new() -> Self {
+        Self {
+            executable: std::option::Option::None,
+            args: std::option::Option::Some(std::vec::Vec::new()),
+            current_dir: std::option::Option::None,
+        }
+    }
+    fn executable(&mut self, executable: String) -> &mut Self {
+        self.executable = std::option::Option::Some(executable);
+        self
+    }
+    fn args(&mut self, args: Vec<String>) -> &mut Self {
+        self.args = std::option::Option::Some(args);
+        self
+    }
+    fn current_dir(&mut self, current_dir: String) -> &mut Self {
+        self.current_dir = std::option::Option::Some(current_dir);
+        self
+    }
+    fn build(
+        &mut self,
+    ) -> std::result::Result<Command, std::boxed::Box<dyn std::error::Error>> {
+        let mut executable;
+        if let Some(v) = self.executable.take() {
+            executable = v;
+        } else {
+            let err = format!("{} field is missing", stringify!(executable));
+            return std::result::Result::Err(err.into());
+        }
+        let mut args;
+        if let Some(v) = self.args.take() {
+            args = v;
+        } else {
+            let err = format!("{} field is missing", stringify!(args));
+            return std::result::Result::Err(err.into());
+        }
+        let current_dir = self.current_dir.take();
+        return std::result::Result::Ok(Command {
+            executable,
+            args,
+            current_dir,
+        });
+    }
+}
+impl std::default::Default for CommandBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl std::clone::Clone for CommandBuilder
+where
+    for<'__builder> String: std::clone::Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            executable: std::clone::Clone::clone(&self.executable),
+            args: std::clone::Clone::clone(&self.args),
+            current_dir: std::clone::Clone::clone(&self.current_dir),
+        }
+    }
+}
+impl std::fmt::Debug for CommandBuilder
+where
+    for<'__builder> String: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommandBuilder")
+            .field("executable", &self.executable)
+            .field("args", &self.args)
+            .field("current_dir", &self.current_dir)
+            .finish()
+    }
+}
+impl Command {
+    fn builder() -> CommandBuilder {
+        CommandBuilder::new()
+    }
+    pub fn build_with<__F>(
+        f: __F,
+    ) -> std::result::Result<Self, std::boxed::Box<dyn std::error::Error>>
+    where
+        __F: for<'__b> std::ops::FnOnce(
+            &'__b mut CommandBuilder,
+        ) -> &'__b mut CommandBuilder,
+    {
+        let mut __builder = CommandBuilder::new();
+        f(&mut __builder).build()
+    }
+    pub fn try_new(
+        executable: String,
+    ) -> std::result::Result<Self, std::boxed::Box<dyn std::error::Error>> {
+        let mut __builder = CommandBuilder::new();
+        __builder.executable(executable);
+        __builder.build()
+    }
+}
@@ -0,0 +1,11 @@
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    /// The program to run.
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(each = "env")]
+    env: Vec<String>,
+}
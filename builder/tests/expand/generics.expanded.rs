@@ -0,0 +1,224 @@
+pub struct EntryBuilder<'a, K: Clone, V = u8>
+where
+    V: Display,
+{
+    key: std::option::Option<K>,
+    label: std::option::Option<&'a str>,
+    value: std::option::Option<V>,
+}
+impl<'a, K: Clone, V> EntryBuilder<'a, K, V>
+where
+    V: Display,
+{
+    pub fn new() -> Self {
+        Self {
+            key: std::option::Option::None,
+            label: std::option::Option::None,
+            value: std::option::Option::None,
+        }
+    }
+    fn key(&mut self, key: K) -> &mut Self {
+        self.key = std::option::Option::Some(key);
+        self
+    }
+    fn label(&mut self, label: &'a str) -> &mut Self {
+        self.label = std::option::Option::Some(label);
+        self
+    }
+    fn value(&mut self, value: V) -> &mut Self {
+        self.value = std::option::Option::Some(value);
+        self
+    }
+    fn build(
+        &mut self,
+    ) -> std::result::Result<Entry<'a, K, V>, std::boxed::Box<dyn std::error::Error>> {
+        let mut key;
+        if let Some(v) = self.key.take() {
+            key = v;
+        } else {
+            let err = format!("{} field is missing", stringify!(key));
+            return std::result::Result::Err(err.into());
+        }
+        let mut label;
+        if let Some(v) = self.label.take() {
+            label = v;
+        } else {
+            let err = format!("{} field is missing", stringify!(label));
+            return std::result::Result::Err(err.into());
+        }
+        let value = self.value.take();
+        return std::result::Result::Ok(Entry { key, label, value });
+    }
+}
+impl<'a, K: Clone, V> std::default::Default for EntryBuilder<'a, K, V>
+where
+    V: Display,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<'a, K: Clone + std::clone::Clone, V: std::clone::Clone> std::clone::Clone
+for EntryBuilder<'a, K, V>
+where
+    V: Display,
+    for<'__builder> &'a str: std::clone::Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            key: std::clone::Clone::clone(&self.key),
+            label: std::clone::Clone::clone(&self.label),
+            value: std::clone::Clone::clone(&self.value),
+        }
+    }
+}
+impl<'a, K: Clone + std::fmt::Debug, V: std::fmt::Debug> std::fmt::Debug
+for EntryBuilder<'a, K, V>
+where
+    V: Display,
+    for<'__builder> &'a str: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EntryBuilder")
+            .field("key", &self.key)
+            .field("label", &self.label)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+impl<'a, K: Clone, V> Entry<'a, K, V>
+where
+    V: Display,
+{
+    fn builder() -> EntryBuilder<'a, K, V> {
+        EntryBuilder::new()
+    }
+    pub fn build_with<__F>(
+        f: __F,
+    ) -> std::result::Result<Self, std::boxed::Box<dyn std::error::Error>>
+    where
+        __F: for<'__b> std::ops::FnOnce(
+            &'__b mut EntryBuilder<'a, K, V>,
+        ) -> &'__b mut EntryBuilder<'a, K, V>,
+    {
+        let mut __builder = EntryBuilder::new();
+        f(&mut __builder).build()
+    }
+    pub fn try_new(
+        key: K,
+        label: &'a str,
+    ) -> std::result::Result<Self, std::boxed::Box<dyn std::error::Error>> {
+        let mut __builder = EntryBuilder::new();
+        __builder.key(key);
+        __builder.label(label);
+        __builder.build()
+    }
+}
+pub struct NextBuilder<I: Iterator> {
+    item: std::option::Option<I::Item>,
+    rest: std::option::Option<Vec<I::Item>>,
+    count: std::option::Option<usize>,
+}
+impl<I: Iterator> NextBuilder<I> {
+    pub fn new() -> Self {
+        Self {
+            item: std::option::Option::None,
+            rest: std::option::Option::Some(std::vec::Vec::new()),
+            count: std::option::Option::None,
+        }
+    }
+    fn item(&mut self, item: I::Item) -> &mut Self {
+        self.item = std::option::Option::Some(item);
+        self
+    }
+    fn rest(&mut self, rest: Vec<I::Item>) -> &mut Self {
+        self.rest = std::option::Option::Some(rest);
+        self
+    }
+    fn count(&mut self, count: usize) -> &mut Self {
+        self.count = std::option::Option::Some(count);
+        self
+    }
+    fn build(
+        &mut self,
+    ) -> std::result::Result<Next<I>, std::boxed::Box<dyn std::error::Error>> {
+        let mut item;
+        if let Some(v) = self.item.take() {
+            item = v;
+        } else {
+            let err = format!("{} field is missing", stringify!(item));
+            return std::result::Result::Err(err.into());
+        }
+        let mut rest;
+        if let Some(v) = self.rest.take() {
+            rest = v;
+        } else {
+            let err = format!("{} field is missing", stringify!(rest));
+            return std::result::Result::Err(err.into());
+        }
+        let mut count;
+        if let Some(v) = self.count.take() {
+            count = v;
+        } else {
+            let err = format!("{} field is missing", stringify!(count));
+            return std::result::Result::Err(err.into());
+        }
+        return std::result::Result::Ok(Next { item, rest, count });
+    }
+}
+impl<I: Iterator> std::default::Default for NextBuilder<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<I: Iterator> std::clone::Clone for NextBuilder<I>
+where
+    I::Item: std::clone::Clone,
+    for<'__builder> usize: std::clone::Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            item: std::clone::Clone::clone(&self.item),
+            rest: std::clone::Clone::clone(&self.rest),
+            count: std::clone::Clone::clone(&self.count),
+        }
+    }
+}
+impl<I: Iterator> std::fmt::Debug for NextBuilder<I>
+where
+    I::Item: std::fmt::Debug,
+    for<'__builder> usize: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NextBuilder")
+            .field("item", &self.item)
+            .field("rest", &self.rest)
+            .field("count", &self.count)
+            .finish()
+    }
+}
+impl<I: Iterator> Next<I> {
+    fn builder() -> NextBuilder<I> {
+        NextBuilder::new()
+    }
+    pub fn build_with<__F>(
+        f: __F,
+    ) -> std::result::Result<Self, std::boxed::Box<dyn std::error::Error>>
+    where
+        __F: for<'__b> std::ops::FnOnce(
+            &'__b mut NextBuilder<I>,
+        ) -> &'__b mut NextBuilder<I>,
+    {
+        let mut __builder = NextBuilder::new();
+        f(&mut __builder).build()
+    }
+    pub fn try_new(
+        item: I::Item,
+        count: usize,
+    ) -> std::result::Result<Self, std::boxed::Box<dyn std::error::Error>> {
+        let mut __builder = NextBuilder::new();
+        __builder.item(item);
+        __builder.count(count);
+        __builder.build()
+    }
+}
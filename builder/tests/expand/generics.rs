@@ -0,0 +1,19 @@
+use derive_builder::Builder;
+use std::fmt::Display;
+
+#[derive(Builder)]
+pub struct Entry<'a, K: Clone, V = u8>
+where
+    V: Display,
+{
+    key: K,
+    label: &'a str,
+    value: Option<V>,
+}
+
+#[derive(Builder)]
+pub struct Next<I: Iterator> {
+    item: I::Item,
+    rest: Vec<I::Item>,
+    count: usize,
+}
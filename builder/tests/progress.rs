@@ -10,4 +10,11 @@ fn tests() {
     t.pass("tests/07-repeated-field.rs");
     t.compile_fail("tests/08-unrecognized-attribute.rs");
     t.pass("tests/09-redefined-prelude-types.rs");
+    t.pass("tests/10-builder-traits.rs");
+    t.pass("tests/11-build-with.rs");
+    t.compile_fail("tests/12-not-named-struct.rs");
+    t.compile_fail("tests/13-builder-attr-not-list.rs");
+    t.compile_fail("tests/14-each-not-string.rs");
+    t.compile_fail("tests/15-each-missing-value.rs");
+    t.compile_fail("tests/16-each-invalid-ident.rs");
 }
@@ -0,0 +1,42 @@
+// Crates that have the "proc-macro" crate type are only allowed to export
+// procedural macros, so the trait implemented by #[derive(DebugFields)] is
+// defined here and the derive from derive_debug is re-exported next to it.
+//
+// A structured logger implements FieldVisitor, or passes a closure, and gets
+// every field as a `(name, &dyn Debug)` pair, formatted the way CustomDebug
+// would print it:
+//
+//     #[derive(DebugFields)]
+//     pub struct Request {
+//         path: String,
+//         #[debug(redact)]
+//         token: String,
+//     }
+//
+//     request.visit_fields(&mut |name: &str, value: &dyn Debug| {
+//         log.push((name.to_owned(), format!("{:?}", value)));
+//     });
+#![no_std]
+
+use core::fmt::Debug;
+
+pub use derive_debug::DebugFields;
+
+pub trait DebugFields {
+    // calls `visitor` once for every field that CustomDebug would print, in
+    // declaration order
+    fn visit_fields(&self, visitor: &mut dyn FieldVisitor);
+}
+
+pub trait FieldVisitor {
+    fn visit_field(&mut self, name: &str, value: &dyn Debug);
+}
+
+impl<F> FieldVisitor for F
+where
+    F: FnMut(&str, &dyn Debug),
+{
+    fn visit_field(&mut self, name: &str, value: &dyn Debug) {
+        self(name, value)
+    }
+}
@@ -0,0 +1,76 @@
+// #[derive(DebugFields)] walks the fields as `(name, &dyn Debug)` pairs, each
+// formatted the way CustomDebug prints it, so a structured logger can emit
+// key/value pairs without parsing Debug output.
+//
+// Skipped fields are left out, `skip_if` is evaluated at runtime, and
+// `redact`, `with`, `rename` and format strings all apply. Tuple fields are
+// named by their index, and for an enum only the fields of the current
+// variant are visited.
+
+use debug_fields::{DebugFields, FieldVisitor};
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+#[derive(CustomDebug, DebugFields)]
+pub struct Request<T> {
+    path: &'static str,
+    #[debug(redact)]
+    token: String,
+    #[debug(rename = "status")]
+    code: u16,
+    #[debug = "{}ms"]
+    elapsed: u64,
+    #[debug(skip_if = "Option::is_none")]
+    query: Option<&'static str>,
+    #[debug(skip)]
+    cache: Vec<u8>,
+    body: T,
+}
+
+#[derive(DebugFields)]
+pub enum Event {
+    Click { x: i32, y: i32 },
+    Key(char),
+    Quit,
+}
+
+// collects `name=value` pairs, like a logger would
+#[derive(Default)]
+struct Pairs(Vec<String>);
+
+impl FieldVisitor for Pairs {
+    fn visit_field(&mut self, name: &str, value: &dyn Debug) {
+        self.0.push(format!("{}={:?}", name, value));
+    }
+}
+
+fn pairs(value: &dyn DebugFields) -> String {
+    let mut pairs = Pairs::default();
+    value.visit_fields(&mut pairs);
+    pairs.0.join(" ")
+}
+
+fn main() {
+    let request = Request {
+        path: "/",
+        token: "secret".to_owned(),
+        code: 200,
+        elapsed: 12,
+        query: None,
+        cache: vec![],
+        body: [1, 2],
+    };
+    assert_eq!(
+        pairs(&request),
+        r#"path="/" token=*** status=200 elapsed=12ms body=[1, 2]"#
+    );
+
+    // a closure works as a visitor too
+    let mut names = vec![];
+    request.visit_fields(&mut |name: &str, _: &dyn Debug| names.push(name.to_owned()));
+    assert_eq!(names, ["path", "token", "status", "elapsed", "body"]);
+
+    assert_eq!(pairs(&Event::Click { x: 1, y: 2 }), "x=1 y=2");
+    assert_eq!(pairs(&Event::Key('q')), "0='q'");
+    assert_eq!(pairs(&Event::Quit), "");
+}
@@ -1,27 +1,62 @@
-use std::collections::HashMap;
-
 use syn::{
     visit::{self, Visit},
     TypePath,
 };
 
+use crate::is_generic_type;
+
+// Collects the associated types of type parameters used in field types, each
+// of them once, in the order they first appear:
+//
+//   - `T::Value`
+//   - qualified projections like `<T as Trait>::Value` or
+//     `<T::Inner as Trait>::Value`, also when nested as in
+//     `Vec<<T as Iterator>::Item>`
+//
+// The same places GenericParamVisiter skips need no bound here either:
+// PhantomData, fn pointers, raw pointers and trait objects.
 pub struct AssociatedTypeVisiter {
     pub generic_type_arr: Vec<String>,
-    pub associated_type_map: HashMap<String, Vec<TypePath>>,
+    pub associated_type_arr: Vec<TypePath>,
+}
+
+impl AssociatedTypeVisiter {
+    fn push(&mut self, i: &TypePath) {
+        if !self.associated_type_arr.contains(i) {
+            self.associated_type_arr.push(i.clone());
+        }
+    }
 }
 
 impl<'ast> Visit<'ast> for AssociatedTypeVisiter {
     fn visit_type_path(&mut self, i: &'ast TypePath) {
-        if i.path.segments.len() >= 2 {
+        if let Some(last) = i.path.segments.last() {
+            if last.ident == "PhantomData" {
+                return;
+            }
+        }
+
+        if let Some(ref qself) = i.qself {
+            if is_generic_type(&qself.ty, &self.generic_type_arr) {
+                self.push(i);
+                return;
+            }
+        } else if i.path.segments.len() >= 2 {
             let generic_type_name = i.path.segments[0].ident.to_string();
             if self.generic_type_arr.contains(&generic_type_name) {
-                self.associated_type_map
-                    .entry(generic_type_name)
-                    .or_insert(vec![])
-                    .push(i.clone());
+                self.push(i);
             }
         }
 
         visit::visit_type_path(self, i);
     }
+
+    fn visit_type_bare_fn(&mut self, _: &'ast syn::TypeBareFn) {}
+
+    // *const T::Value prints the address whatever the projection is
+    fn visit_type_ptr(&mut self, _: &'ast syn::TypePtr) {}
+
+    fn visit_type_trait_object(&mut self, _: &'ast syn::TypeTraitObject) {}
+
+    fn visit_type_impl_trait(&mut self, _: &'ast syn::TypeImplTrait) {}
 }
@@ -0,0 +1,192 @@
+// #[derive(CustomDisplay)], implementing Display from a template given on the
+// struct, or on each variant of an enum:
+//
+//     #[display("{name} <{email}>")]
+//     struct User { name: String, email: String }
+//
+// Fields are referenced by name, tuple fields by index like `{0}` or `{}`.
+
+use crate::format_spec::{self, Arg};
+use crate::{FieldsStyle, StructAttr, VariantInfo};
+use quote::{format_ident, quote};
+
+pub fn do_expand(ast: syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &ast.ident;
+
+    // shares the field collection with CustomDebug, so #[debug(...)] on a
+    // field is still checked but has no effect on the Display output
+    let variant_info_arr = crate::get_variant_info_arr(&ast, &StructAttr::default())?;
+    let struct_display_attr = get_display_attr(&ast.attrs)?;
+
+    let variant_attr_arr = match &ast.data {
+        syn::Data::Struct(syn::DataStruct { fields, .. }) => {
+            check_field_arr(fields)?;
+            vec![(struct_display_attr, quote!(#ident))]
+        }
+        syn::Data::Enum(syn::DataEnum { variants, .. }) => variants
+            .iter()
+            .map(|v| {
+                check_field_arr(&v.fields)?;
+                // a template on the enum is used for variants without their own
+                let display_attr = get_display_attr(&v.attrs)?.or(struct_display_attr.clone());
+                let v_ident = &v.ident;
+                Ok((display_attr, quote!(#v_ident)))
+            })
+            .collect::<syn::Result<Vec<_>>>()?,
+        syn::Data::Union(data_union) => return Err(crate::unsupported_union_error(data_union)),
+    };
+
+    let mut generics = ast.generics.clone();
+    let mut arm_ts = proc_macro2::TokenStream::new();
+    let mut bound_ty_arr: Vec<(syn::Path, Vec<&syn::Type>)> = vec![];
+    for (variant_info, (display_attr, spanned)) in
+        variant_info_arr.iter().zip(variant_attr_arr.iter())
+    {
+        let display_attr = match display_attr {
+            Some(display_attr) => display_attr,
+            None => {
+                return Err(syn::Error::new_spanned(
+                    spanned,
+                    r#"missing `#[display("...")]` template"#,
+                ))
+            }
+        };
+
+        let (arm, used_field_arr) = impl_display_arm(variant_info, display_attr)?;
+        arm_ts.extend(arm);
+
+        for (idx, format_trait) in used_field_arr {
+            crate::push_bound_ty(
+                &mut bound_ty_arr,
+                format_spec::get_trait_path(&format_trait),
+                &variant_info.field_info_arr[idx].ty,
+            );
+        }
+    }
+
+    for (trait_path, ty_arr) in bound_ty_arr.iter() {
+        crate::add_inferred_bounds(&mut generics, ty_arr, trait_path);
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let formatter = crate::formatter_ident();
+    let match_ts = crate::impl_match(&variant_info_arr, arm_ts);
+
+    Ok(quote!(
+        impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+            fn fmt(&self, #formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #match_ts
+            }
+        }
+    ))
+}
+
+// Name { name: __self_0, email: __self_1, .. } => write!(f, "{name} <{email}>",
+//     name = __self_0, email = __self_1),
+//
+// Tuple fields become named arguments as well, `{0}` is rewritten to `{_0}`,
+// so that fields the template doesn't use are never passed to `write!`.
+// Returns the arm along with the index and format trait of every field the
+// template formats.
+fn impl_display_arm(
+    variant_info: &VariantInfo,
+    display_attr: &syn::LitStr,
+) -> syn::Result<(proc_macro2::TokenStream, Vec<(usize, String)>)> {
+    let VariantInfo {
+        path,
+        ident_str,
+        style,
+        field_info_arr,
+    } = variant_info;
+
+    let mut next_idx = 0;
+    let mut arg_idx_arr: Vec<usize> = vec![];
+    let mut used_field_arr: Vec<(usize, String)> = vec![];
+    let template = format_spec::walk(&display_attr.value(), &mut |arg, format_trait| {
+        let (idx, replacement) = match arg {
+            Arg::Name(name) => {
+                let idx = field_info_arr
+                    .iter()
+                    .position(|f| crate::is_member_named(&f.member, &name))
+                    .ok_or_else(|| format!("`{}` has no field named `{}`", ident_str, name))?;
+                (idx, None)
+            }
+            Arg::Next | Arg::Index(_) => {
+                let idx = match arg {
+                    Arg::Index(idx) => idx,
+                    _ => {
+                        next_idx += 1;
+                        next_idx - 1
+                    }
+                };
+                if style == &FieldsStyle::Named {
+                    return Err(format!(
+                        "`{}` has named fields, refer to them by name like `{{field}}`",
+                        ident_str
+                    ));
+                }
+                if idx >= field_info_arr.len() {
+                    return Err(format!("`{}` has no field {}", ident_str, idx));
+                }
+                (idx, Some(format!("_{}", idx)))
+            }
+        };
+
+        if !arg_idx_arr.contains(&idx) {
+            arg_idx_arr.push(idx);
+        }
+        // widths and precisions have to be usize, they need no bound
+        if let Some(format_trait) = format_trait {
+            used_field_arr.push((idx, format_trait.to_string()));
+        }
+        Ok(replacement)
+    })
+    .map_err(|msg| syn::Error::new(display_attr.span(), msg))?;
+    let template = syn::LitStr::new(&template, display_attr.span());
+
+    let formatter = crate::formatter_ident();
+    let mut pat_ts = proc_macro2::TokenStream::new();
+    let mut arg_ts = proc_macro2::TokenStream::new();
+    for idx in arg_idx_arr {
+        let f = &field_info_arr[idx];
+        let member = &f.member;
+        let binding = &f.binding;
+        let name = match member {
+            syn::Member::Named(ident) => ident.clone(),
+            syn::Member::Unnamed(_) => format_ident!("_{}", idx),
+        };
+        pat_ts.extend(quote!(#member: #binding,));
+        arg_ts.extend(quote!(, #name = #binding));
+    }
+
+    Ok((
+        quote!(
+            #path { #pat_ts .. } => ::core::write!(#formatter, #template #arg_ts),
+        ),
+        used_field_arr,
+    ))
+}
+
+// #[display("...")]
+fn get_display_attr(attrs: &[syn::Attribute]) -> syn::Result<Option<syn::LitStr>> {
+    let mut result = None;
+    for attr in attrs.iter() {
+        if attr.path().is_ident("display") {
+            result = Some(attr.parse_args()?);
+        }
+    }
+    Ok(result)
+}
+
+// the template goes on the struct or on a variant, never on a field
+fn check_field_arr(fields: &syn::Fields) -> syn::Result<()> {
+    for attr in fields.iter().flat_map(|f| f.attrs.iter()) {
+        if attr.path().is_ident("display") {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "#[display] goes on the struct or on an enum variant, not on a field",
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,76 @@
+// #[derive(DebugFields)], implementing `debug_fields::DebugFields` so that a
+// structured logger can walk the fields as `(name, &dyn Debug)` pairs instead
+// of parsing the Debug output. Each field is passed the way CustomDebug would
+// print it, so skip, skip_if, redact, with, rename and format strings apply.
+//
+// The trait lives in the debug-fields crate, proc-macro crates can't export
+// one, and the derive is meant to be used through its re-export there.
+
+use crate::{FieldsStyle, VariantInfo};
+use quote::quote;
+
+pub fn do_expand(ast: syn::DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let ident = &ast.ident;
+    let struct_attr = crate::get_struct_attr(&ast)?;
+    let variant_info_arr = crate::get_variant_info_arr(&ast, &struct_attr)?;
+
+    let generics = crate::get_debug_generics(&struct_attr, &ast.generics, &variant_info_arr);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let visitor = syn::Ident::new("visitor", proc_macro2::Span::mixed_site());
+
+    let mut arm_ts = proc_macro2::TokenStream::new();
+    for variant_info in variant_info_arr.iter() {
+        arm_ts.extend(impl_visit_arm(variant_info, struct_attr.packed, &visitor));
+    }
+
+    let helper_ts = crate::impl_field_value_helper(&variant_info_arr);
+    let match_ts = crate::impl_match(&variant_info_arr, arm_ts);
+
+    Ok(quote!(
+        impl #impl_generics ::debug_fields::DebugFields for #ident #ty_generics #where_clause {
+            fn visit_fields(&self, #visitor: &mut dyn ::debug_fields::FieldVisitor) {
+                #helper_ts
+                #match_ts
+            }
+        }
+    ))
+}
+
+// Name { x: __self_0, y: __self_1, .. } => {
+//     visitor.visit_field("x", __self_0);
+//     visitor.visit_field("y", __self_1);
+// }
+//
+// Tuple fields are named by their index, "0", "1", ...
+fn impl_visit_arm(
+    variant_info: &VariantInfo,
+    is_packed: bool,
+    visitor: &syn::Ident,
+) -> proc_macro2::TokenStream {
+    let VariantInfo {
+        path,
+        style,
+        field_info_arr,
+        ..
+    } = variant_info;
+
+    let (pat_ts, let_ts) = crate::bind_field_arr(field_info_arr, is_packed);
+    let mut field_ts = proc_macro2::TokenStream::new();
+    for f in field_info_arr.iter().filter(|f| !f.skip) {
+        let f_ident_str = match f.rename {
+            Some(ref rename) if style == &FieldsStyle::Named => rename.value(),
+            _ => crate::member_to_string(&f.member),
+        };
+        let value = crate::field_value(f, field_info_arr);
+        let field = quote!(#visitor.visit_field(#f_ident_str, #value););
+        field_ts.extend(crate::wrap_skip_if(f, field));
+    }
+
+    quote!(
+        #path { #pat_ts .. } => {
+            #let_ts
+            #field_ts
+        }
+    )
+}
@@ -0,0 +1,273 @@
+// Parses the format strings given in attributes, `#[debug = "..."]` and
+// `#[display("...")]`, so that mistakes are reported on the string literal
+// instead of deep inside the generated `format_args!`.
+
+use syn::{ext::IdentExt, parse_quote};
+
+const FORMAT_TRAIT_ARR: [&str; 11] = ["", "?", "x?", "X?", "x", "X", "o", "b", "e", "E", "p"];
+
+// An argument referenced from a format string.
+pub enum Arg {
+    // `{}`, the next positional argument
+    Next,
+    // `{0}` or `0$`
+    Index(usize),
+    // `{name}` or `name$`
+    Name(String),
+}
+
+type OnArg<'a> = dyn FnMut(Arg, Option<&str>) -> Result<Option<String>, String> + 'a;
+
+// The arguments of a `#[debug = "..."]` format string.
+pub struct DebugFormat {
+    // other fields passed as named arguments, `{name}`, `{:name$}` or
+    // `{:.name$}`
+    pub named_arg_arr: Vec<String>,
+    // the argument of every placeholder along with the trait it is formatted
+    // with, None for the field value itself
+    pub format_trait_arr: Vec<(Option<String>, syn::Path)>,
+}
+
+// For `#[debug = "..."]`. The field value is the only positional argument, so
+// the string must contain exactly one `{}` (or `{0}`) placeholder. Any
+// other argument refers to another field by name.
+pub fn get_debug_format(lit: &syn::LitStr) -> syn::Result<DebugFormat> {
+    let mut named_arg_arr = vec![];
+    let mut format_trait_arr = vec![];
+    let mut positional_count = 0;
+
+    walk(&lit.value(), &mut |arg, format_trait| {
+        match arg {
+            Arg::Next | Arg::Index(0) => {
+                if let Some(format_trait) = format_trait {
+                    positional_count += 1;
+                    format_trait_arr.push((None, get_trait_path(format_trait)));
+                }
+            }
+            Arg::Index(idx) => {
+                return Err(format!(
+                    "there is no argument {}, the field value is argument 0",
+                    idx
+                ))
+            }
+            Arg::Name(name) => {
+                // widths and precisions have to be usize, they need no bound
+                if let Some(format_trait) = format_trait {
+                    format_trait_arr.push((Some(name.clone()), get_trait_path(format_trait)));
+                }
+                if !named_arg_arr.contains(&name) {
+                    named_arg_arr.push(name);
+                }
+            }
+        }
+        Ok(None)
+    })
+    .map_err(|msg| syn::Error::new(lit.span(), msg))?;
+
+    if positional_count != 1 {
+        return Err(syn::Error::new(
+            lit.span(),
+            format!(
+                "expected exactly one `{{}}` placeholder for the field value, found {}",
+                positional_count
+            ),
+        ));
+    }
+
+    Ok(DebugFormat {
+        named_arg_arr,
+        format_trait_arr,
+    })
+}
+
+// the trait a placeholder like `{:x}` formats its argument with
+pub fn get_trait_path(format_trait: &str) -> syn::Path {
+    match format_trait {
+        "?" | "x?" | "X?" => parse_quote!(::core::fmt::Debug),
+        "x" => parse_quote!(::core::fmt::LowerHex),
+        "X" => parse_quote!(::core::fmt::UpperHex),
+        "o" => parse_quote!(::core::fmt::Octal),
+        "b" => parse_quote!(::core::fmt::Binary),
+        "e" => parse_quote!(::core::fmt::LowerExp),
+        "E" => parse_quote!(::core::fmt::UpperExp),
+        "p" => parse_quote!(::core::fmt::Pointer),
+        _ => parse_quote!(::core::fmt::Display),
+    }
+}
+
+// Calls `on_arg` for every argument the format string refers to, along with
+// the format trait used for it (`""` for Display, `"?"` for Debug, ...), or
+// None when the argument is a width or precision. Returns the format string
+// with every argument `on_arg` gave a replacement for rewritten.
+pub fn walk(s: &str, on_arg: &mut OnArg) -> Result<String, String> {
+    let mut result = String::new();
+
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        result.push(c);
+        match c {
+            '{' => {
+                if chars.peek() == Some(&'{') {
+                    result.push('{');
+                    chars.next();
+                    continue;
+                }
+
+                let mut placeholder = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => return Err("unterminated `{` in format string".to_string()),
+                    }
+                }
+
+                let (arg, spec) = match placeholder.split_once(':') {
+                    Some((arg, spec)) => (arg, Some(spec)),
+                    None => (placeholder.as_str(), None),
+                };
+
+                // the spec has to be walked first to know the format trait
+                let mut spec_result = String::new();
+                let format_trait = walk_spec(spec.unwrap_or(""), &mut spec_result, on_arg)?;
+
+                let replacement = if arg.is_empty() {
+                    on_arg(Arg::Next, Some(&format_trait))?
+                } else {
+                    on_arg(to_arg(arg)?, Some(&format_trait))?
+                };
+                result.push_str(replacement.as_deref().unwrap_or(arg));
+
+                if spec.is_some() {
+                    result.push(':');
+                    result.push_str(&spec_result);
+                }
+                result.push('}');
+            }
+            '}' => match chars.next() {
+                Some('}') => result.push('}'),
+                _ => {
+                    return Err("unmatched `}` in format string, use `}}` to print `}`".to_string())
+                }
+            },
+            _ => {}
+        }
+    }
+
+    Ok(result)
+}
+
+// [[fill]align][sign]['#']['0'][width]['.' precision][type], returning the
+// format trait
+fn walk_spec(spec: &str, result: &mut String, on_arg: &mut OnArg) -> Result<String, String> {
+    let spec = spec.chars().collect::<Vec<_>>();
+    let mut idx = 0;
+
+    // fill and align
+    if spec.len() >= 2 && "<^>".contains(spec[1]) {
+        idx = 2;
+    } else if !spec.is_empty() && "<^>".contains(spec[0]) {
+        idx = 1;
+    }
+
+    // sign
+    if idx < spec.len() && "+-".contains(spec[idx]) {
+        idx += 1;
+    }
+
+    // alternate
+    if idx < spec.len() && spec[idx] == '#' {
+        idx += 1;
+    }
+
+    // zero padding, unless it is the `0$` width
+    if idx < spec.len() && spec[idx] == '0' && spec.get(idx + 1) != Some(&'$') {
+        idx += 1;
+    }
+    result.extend(&spec[..idx]);
+
+    // width
+    idx = walk_count(&spec, idx, result, on_arg)?;
+
+    // precision
+    if idx < spec.len() && spec[idx] == '.' {
+        result.push('.');
+        idx += 1;
+        if spec.get(idx) == Some(&'*') {
+            return Err(
+                "`.*` takes the precision from an extra argument, use `.name$` instead".to_string(),
+            );
+        }
+        let count_idx = walk_count(&spec, idx, result, on_arg)?;
+        if count_idx == idx {
+            return Err("expected precision after `.` in format string".to_string());
+        }
+        idx = count_idx;
+    }
+
+    let format_trait = spec[idx..].iter().collect::<String>();
+    if !FORMAT_TRAIT_ARR.contains(&format_trait.as_str()) {
+        return Err(format!(
+            "unknown format trait `{}` in format string",
+            format_trait
+        ));
+    }
+    result.push_str(&format_trait);
+
+    Ok(format_trait)
+}
+
+// `8`, `0$` or `name$`, returning the index after the count
+fn walk_count(
+    spec: &[char],
+    idx: usize,
+    result: &mut String,
+    on_arg: &mut OnArg,
+) -> Result<usize, String> {
+    let mut end = idx;
+    if spec.get(idx).is_some_and(|c| c.is_ascii_digit()) {
+        while end < spec.len() && spec[end].is_ascii_digit() {
+            end += 1;
+        }
+    } else {
+        while end < spec.len() && (spec[end].is_alphanumeric() || spec[end] == '_') {
+            end += 1;
+        }
+    }
+
+    let count = spec[idx..end].iter().collect::<String>();
+    if end == idx || spec.get(end) != Some(&'$') {
+        if count.chars().all(|c| c.is_ascii_digit()) {
+            // a literal width like `8`
+            result.push_str(&count);
+            return Ok(end);
+        }
+        // not a count at all, probably the format trait
+        return Ok(idx);
+    }
+
+    let replacement = on_arg(to_arg(&count)?, None)?;
+    result.push_str(replacement.as_deref().unwrap_or(&count));
+    result.push('$');
+    Ok(end + 1)
+}
+
+fn to_arg(arg: &str) -> Result<Arg, String> {
+    if arg.chars().all(|c| c.is_ascii_digit()) {
+        return arg
+            .parse()
+            .map(Arg::Index)
+            .map_err(|_| format!("invalid argument `{}` in format string", arg));
+    }
+    // `{type}` names the field `r#type`, a raw identifier isn't valid in a
+    // format string
+    match syn::parse::Parser::parse_str(syn::Ident::parse_any, arg) {
+        Ok(ident) if !arg.starts_with("r#") => Ok(Arg::Name(ident.to_string())),
+        Ok(ident) => Err(format!(
+            "invalid argument `{}` in format string, write `{}` instead",
+            arg,
+            ident.unraw()
+        )),
+        Err(_) => Err(format!("invalid argument `{}` in format string", arg)),
+    }
+}
@@ -0,0 +1,54 @@
+use syn::{
+    visit::{self, Visit},
+    Ident, TypePath,
+};
+
+// Collects every type parameter that is used directly somewhere inside a field
+// type, at any depth: `T`, `Vec<T>`, `HashMap<K, V>`, `(T, U)`, `[T; N]`,
+// `&'a T`. These are the parameters that need a `Debug` bound.
+//
+// A few places don't need the bound even though the parameter shows up:
+// anything inside `PhantomData<..>`, associated types like `T::Value` (the
+// bound goes on the projection, see AssociatedTypeVisiter), fn pointers, raw
+// pointers, and trait objects.
+pub struct GenericParamVisiter {
+    pub generic_type_arr: Vec<String>,
+    pub generic_param_arr: Vec<Ident>,
+}
+
+impl<'ast> Visit<'ast> for GenericParamVisiter {
+    fn visit_type_path(&mut self, i: &'ast TypePath) {
+        // <T as Trait>::Value
+        if i.qself.is_some() {
+            return;
+        }
+
+        if let Some(last) = i.path.segments.last() {
+            if last.ident == "PhantomData" {
+                return;
+            }
+        }
+
+        if let Some(first) = i.path.segments.first() {
+            if self.generic_type_arr.contains(&first.ident.to_string()) {
+                if i.path.segments.len() == 1 && !self.generic_param_arr.contains(&first.ident) {
+                    self.generic_param_arr.push(first.ident.clone());
+                }
+                // T::Value is left to AssociatedTypeVisiter
+                return;
+            }
+        }
+
+        visit::visit_type_path(self, i);
+    }
+
+    // fn(T) -> U is Debug whatever T and U are
+    fn visit_type_bare_fn(&mut self, _: &'ast syn::TypeBareFn) {}
+
+    // *const T prints the address whatever T is
+    fn visit_type_ptr(&mut self, _: &'ast syn::TypePtr) {}
+
+    fn visit_type_trait_object(&mut self, _: &'ast syn::TypeTraitObject) {}
+
+    fn visit_type_impl_trait(&mut self, _: &'ast syn::TypeImplTrait) {}
+}
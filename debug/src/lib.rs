@@ -1,14 +1,99 @@
 mod associated_type_visiter;
-
-use std::collections::HashMap;
+mod display;
+mod fields;
+mod format_spec;
+mod generic_param_visiter;
 
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, parse_quote, visit::Visit, DeriveInput};
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    ext::IdentExt, parse_macro_input, parse_quote, punctuated::Punctuated, visit::Visit,
+    DeriveInput,
+};
 
 struct FieldInfo {
-    ident: syn::Ident,
-    debug_attr: Option<String>,
+    member: syn::Member,
+    ty: syn::Type,
+    // name the field is bound to in the generated `match self` arm
+    binding: syn::Ident,
+    // #[debug = "..."] or #[debug(fmt = "...")]
+    debug_attr: Option<syn::LitStr>,
+    // other fields referenced by name from `debug_attr`, like `{len}`, as
+    // written in the format string, so `type` for a field `r#type`
+    named_arg_arr: Vec<String>,
+    // the trait every placeholder of `debug_attr` formats with, None for the
+    // field itself and the name of another field otherwise
+    format_trait_arr: Vec<(Option<String>, syn::Path)>,
+    // #[debug(skip)]
+    skip: bool,
+    // #[debug(skip_if = "Option::is_none")], a `fn(&T) -> bool`
+    skip_if: Option<syn::ExprPath>,
+    // #[debug(redact)] or #[debug(redact = "...")], the placeholder to print
+    redact: Option<String>,
+    // #[debug(with = "path::to::fn")]
+    with: Option<syn::ExprPath>,
+    // #[debug(rename = "...")]
+    rename: Option<syn::LitStr>,
+    // #[debug(max_len = 64)], for anything that derefs to `str` or `[u8]`
+    max_len: Option<usize>,
+    // #[debug(max_items = 10)], for anything `&T: IntoIterator`
+    max_items: Option<usize>,
+    // #[debug(bound = "...")], replaces the bounds inferred from this field
+    bound: Option<Punctuated<syn::WherePredicate, syn::Token![,]>>,
+}
+
+impl FieldInfo {
+    // whether bounds should be inferred from the field type, fields that are
+    // not printed through their Debug impl don't need any
+    fn is_bound_inferred(&self) -> bool {
+        self.bound.is_none() && !self.skip && self.redact.is_none() && self.with.is_none()
+    }
+}
+
+#[derive(PartialEq, Eq)]
+enum FieldsStyle {
+    Named,   // Name { x: .. }
+    Unnamed, // Name(..)
+    Unit,    // Name
+}
+
+#[derive(Default, PartialEq, Eq)]
+enum BoundsStrategy {
+    // T: Debug for every type parameter used by a field
+    #[default]
+    Params,
+    // FieldTy: Debug for every field type that involves a type parameter
+    Fields,
+}
+
+#[derive(Default)]
+struct StructAttr {
+    // #[debug(bound = "...")]
+    bound: Option<Punctuated<syn::WherePredicate, syn::Token![,]>>,
+    // #[debug(bounds = "params")] or #[debug(bounds = "fields")]
+    bounds: BoundsStrategy,
+    // #[debug(redact_all_except(a, b))]
+    redact_all_except: Option<Vec<syn::Ident>>,
+    // #[debug(rename = "...")]
+    rename: Option<syn::LitStr>,
+    // #[debug(compact)], single line output even under `{:#?}`
+    compact: bool,
+    // #[debug(transparent)], a newtype printed as its only field
+    transparent: bool,
+    // #[debug(with = "path::to::fn")], formats the whole value
+    with: Option<syn::ExprPath>,
+    // #[repr(packed)], fields can't be borrowed and are copied instead
+    packed: bool,
+}
+
+const REDACT_PLACEHOLDER: &str = "***";
+
+struct VariantInfo {
+    // path used in the pattern, `Name` for a struct and `Name::V` for a variant
+    path: syn::Path,
+    ident_str: String,
+    style: FieldsStyle,
+    field_info_arr: Vec<FieldInfo>,
 }
 
 #[proc_macro_derive(CustomDebug, attributes(debug))]
@@ -21,40 +106,47 @@ pub fn derive(input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(CustomDisplay, attributes(display))]
+pub fn derive_display(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    match display::do_expand(ast) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(DebugFields, attributes(debug))]
+pub fn derive_fields(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    match fields::do_expand(ast) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
 fn do_expand(ast: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let ident = &ast.ident;
-    let field_info_arr = get_field_info_arr(&ast)?;
+    let struct_attr = get_struct_attr(&ast)?;
 
-    let none_phantom_generic_param_arr = get_none_phantom_generic_param_arr(&ast);
-
-    let generic_type_arr: Vec<String> = ast
-        .generics
-        .params
-        .iter()
-        .filter_map(|f| {
-            if let syn::GenericParam::Type(ty) = f {
-                Some(ty.ident.to_string())
-            } else {
-                None
-            }
-        })
-        .collect();
+    // a union doesn't know which of its fields is valid, so it is either
+    // printed as `Name { .. }` or handed to #[debug(with = "...")]
+    if let syn::Data::Union(syn::DataUnion { ref fields, .. }) = ast.data {
+        // never printed, but a malformed attribute is still reported
+        for (idx, f) in fields.named.iter().enumerate() {
+            get_field_info(f, idx)?;
+        }
+        return Ok(impl_debug_opaque(&struct_attr, ident, &ast.generics));
+    }
 
-    let mut associated_type_visiter = associated_type_visiter::AssociatedTypeVisiter {
-        generic_type_arr,
-        associated_type_map: HashMap::new(),
-    };
+    let variant_info_arr = get_variant_info_arr(&ast, &struct_attr)?;
 
-    associated_type_visiter.visit_derive_input(&ast);
+    if struct_attr.with.is_some() {
+        return Ok(impl_debug_opaque(&struct_attr, ident, &ast.generics));
+    }
 
-    let impl_debug = impl_debug(
-        &ast,
-        &ident,
-        &ast.generics,
-        &field_info_arr,
-        &none_phantom_generic_param_arr,
-        &associated_type_visiter.associated_type_map,
-    )?;
+    let impl_debug = impl_debug(&struct_attr, ident, &ast.generics, &variant_info_arr)?;
 
     Ok(quote!(
         #impl_debug
@@ -62,194 +154,1018 @@ fn do_expand(ast: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
 }
 
 fn impl_debug(
-    ast: &DeriveInput,
+    struct_attr: &StructAttr,
     ident: &syn::Ident,
     generics: &syn::Generics,
-    field_info_arr: &Vec<FieldInfo>,
-    none_phantom_generic_param_arr: &Vec<syn::Ident>,
-    associated_type_map: &HashMap<String, Vec<syn::TypePath>>,
+    variant_info_arr: &[VariantInfo],
 ) -> syn::Result<proc_macro2::TokenStream> {
-    let ident_str = &ident.to_string();
+    let generics = get_debug_generics(struct_attr, generics, variant_info_arr);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut arm_ts = proc_macro2::TokenStream::new();
+    for variant_info in variant_info_arr.iter() {
+        if struct_attr.transparent {
+            arm_ts.extend(impl_debug_transparent_arm(
+                variant_info,
+                struct_attr.packed,
+            )?);
+        } else {
+            arm_ts.extend(impl_debug_arm(variant_info, struct_attr.packed));
+        }
+    }
+
+    let formatter = formatter_ident();
+
+    // formatting through `{:?}` starts over with a formatter that has the
+    // alternate flag cleared
+    let compact_ts = if struct_attr.compact {
+        quote!(if #formatter.alternate() {
+            return ::core::write!(#formatter, "{:?}", self);
+        })
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+
+    let helper_ts = impl_field_value_helper(variant_info_arr);
+    let match_ts = impl_match(variant_info_arr, arm_ts);
+
+    Ok(quote!(
+        impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #where_clause {
+            fn fmt(&self, #formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #compact_ts
+                #helper_ts
+                #match_ts
+            }
+        }
+    ))
+}
 
+// the impl's generics with the bounds from #[debug(bound = "...")], or the
+// ones inferred according to #[debug(bounds = "...")]
+fn get_debug_generics(
+    struct_attr: &StructAttr,
+    generics: &syn::Generics,
+    variant_info_arr: &[VariantInfo],
+) -> syn::Generics {
     let mut generics = generics.clone();
 
-    if let Some(ref s) = get_struct_escape_hatch(&ast) {
-        generics.make_where_clause();
-        if let Some(w) = generics.where_clause.as_mut() {
-            if let Ok(s) = syn::parse_str(s) {
-                w.predicates.push(s);
+    if let Some(ref bound) = struct_attr.bound {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(bound.iter().cloned());
+    } else if struct_attr.bounds == BoundsStrategy::Fields {
+        let generic_type_arr = generics
+            .type_params()
+            .map(|t| t.ident.to_string())
+            .collect::<Vec<_>>();
+
+        let mut predicate_arr: Vec<syn::WherePredicate> = vec![];
+        for (trait_path, ty_arr) in get_bound_ty_arr(variant_info_arr) {
+            for ty in ty_arr {
+                let predicate = parse_quote!(#ty: #trait_path);
+                if is_generic_type(ty, &generic_type_arr) && !predicate_arr.contains(&predicate) {
+                    predicate_arr.push(predicate);
+                }
             }
         }
+
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(predicate_arr);
     } else {
-        for g in generics.params.iter_mut() {
-            if let syn::GenericParam::Type(t) = g {
-                if none_phantom_generic_param_arr.contains(&t.ident)
-                    && !associated_type_map.contains_key(&t.ident.to_string())
-                {
-                    t.bounds.push(parse_quote!(std::fmt::Debug));
+        for (trait_path, ty_arr) in get_bound_ty_arr(variant_info_arr) {
+            add_inferred_bounds(&mut generics, &ty_arr, &trait_path);
+        }
+    }
+
+    // per-field #[debug(bound = "...")], `bound = ""` adds nothing
+    for f in variant_info_arr
+        .iter()
+        .flat_map(|v| v.field_info_arr.iter())
+    {
+        if let Some(ref bound) = f.bound {
+            generics
+                .make_where_clause()
+                .predicates
+                .extend(bound.iter().cloned());
+        }
+    }
+
+    generics
+}
+
+// The field types to infer bounds from, grouped by the trait they need. A
+// field is printed through its Debug impl, unless a `#[debug = "..."]`
+// format string uses another trait, like `{:x}`, or refers to other fields
+// by name, which then need the trait of their placeholder as well.
+fn get_bound_ty_arr(variant_info_arr: &[VariantInfo]) -> Vec<(syn::Path, Vec<&syn::Type>)> {
+    let mut result: Vec<(syn::Path, Vec<&syn::Type>)> = vec![];
+    for v in variant_info_arr.iter() {
+        for f in v.field_info_arr.iter().filter(|f| f.is_bound_inferred()) {
+            if f.debug_attr.is_none() {
+                push_bound_ty(&mut result, parse_quote!(::core::fmt::Debug), &f.ty);
+                continue;
+            }
+
+            for (name, trait_path) in f.format_trait_arr.iter() {
+                let ty = match name {
+                    Some(name) => v
+                        .field_info_arr
+                        .iter()
+                        .find(|f| is_member_named(&f.member, name))
+                        .map(|f| &f.ty),
+                    None => Some(&f.ty),
+                };
+                // a missing field is reported by get_variant_info
+                if let Some(ty) = ty {
+                    push_bound_ty(&mut result, trait_path.clone(), ty);
                 }
             }
         }
+    }
 
-        generics.make_where_clause();
-        for (_, associated_type_arr) in associated_type_map.iter() {
-            for associated_type in associated_type_arr {
-                if let Some(w) = generics.where_clause.as_mut() {
-                    w.predicates
-                        .push(parse_quote!(#associated_type: std::fmt::Debug));
+    result
+}
+
+// adds `ty` to the types bounded by `trait_path`
+pub(crate) fn push_bound_ty<'a>(
+    bound_ty_arr: &mut Vec<(syn::Path, Vec<&'a syn::Type>)>,
+    trait_path: syn::Path,
+    ty: &'a syn::Type,
+) {
+    match bound_ty_arr.iter_mut().find(|(p, _)| p == &trait_path) {
+        Some((_, ty_arr)) => ty_arr.push(ty),
+        None => bound_ty_arr.push((trait_path, vec![ty])),
+    }
+}
+
+// the items that the expressions from field_value() rely on, only emitted
+// when some field needs them
+fn impl_field_value_helper(variant_info_arr: &[VariantInfo]) -> proc_macro2::TokenStream {
+    let mut result = proc_macro2::TokenStream::new();
+
+    // adapter that lets a `fn(&T, &mut Formatter) -> Result` from
+    // #[debug(with = "...")] be passed to `.field(..)`
+    let is_with_found = variant_info_arr
+        .iter()
+        .flat_map(|v| v.field_info_arr.iter())
+        .any(|f| f.with.is_some());
+    if is_with_found {
+        result.extend(quote!(
+            struct __DebugWith<'a, T: ?Sized>(
+                &'a T,
+                fn(&T, &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result,
+            );
+
+            impl<'a, T: ?Sized> ::core::fmt::Debug for __DebugWith<'a, T> {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    (self.1)(self.0, f)
                 }
             }
-        }
+        ));
     }
 
-    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let is_truncate_found = variant_info_arr
+        .iter()
+        .flat_map(|v| v.field_info_arr.iter())
+        .any(|f| f.max_len.is_some() || f.max_items.is_some());
+    if is_truncate_found {
+        result.extend(impl_truncate_helper());
+    }
 
-    let mut struct_ts = proc_macro2::TokenStream::new();
-    struct_ts.extend(quote!(
-        f.debug_struct(#ident_str)
-    ));
+    result
+}
+
+// Name { x: __self_0, y: __self_1, .. } => {
+//     let mut __debug = f.debug_struct("Name");
+//     __debug.field("x", __self_0);
+//     if !Option::is_none(__self_1) {
+//         __debug.field("y", __self_1);
+//     }
+//     __debug.finish()
+// }
+fn impl_debug_arm(variant_info: &VariantInfo, is_packed: bool) -> proc_macro2::TokenStream {
+    let VariantInfo {
+        path,
+        ident_str,
+        style,
+        field_info_arr,
+    } = variant_info;
 
-    // .field("x", &self.x)
-    // .field("y", &self.y)
+    let formatter = formatter_ident();
+    let (pat_ts, let_ts) = bind_field_arr(field_info_arr, is_packed);
     let mut field_ts = proc_macro2::TokenStream::new();
+    let mut is_skip_found = false;
     for f in field_info_arr.iter() {
-        let f_ident = &f.ident;
-        let f_ident_str = &f.ident.to_string();
-        if let Some(ref debug_attr) = f.debug_attr {
-            field_ts.extend(quote!(
-                .field(#f_ident_str, &format_args!(#debug_attr, &self.#f_ident))
-            ));
-        } else {
-            field_ts.extend(quote!(
-                .field(#f_ident_str, &self.#f_ident)
-            ));
+        let FieldInfo {
+            member,
+            skip,
+            rename,
+            ..
+        } = f;
+
+        if *skip {
+            is_skip_found = true;
+            continue;
         }
+
+        let value = field_value(f, field_info_arr);
+
+        let field = if style == &FieldsStyle::Named {
+            let f_ident_str = match rename {
+                Some(rename) => rename.value(),
+                None => member_to_string(member),
+            };
+            quote!(__debug.field(#f_ident_str, #value);)
+        } else {
+            quote!(__debug.field(#value);)
+        };
+
+        // #[debug(skip_if = "...")] is decided at runtime and leaves no `..`
+        field_ts.extend(wrap_skip_if(f, field));
     }
 
-    Ok(quote!(
-        impl #impl_generics std::fmt::Debug for #ident #ty_generics #where_clause {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                #struct_ts
+    // skipped fields show up as `..` in the output
+    let finish = if is_skip_found {
+        quote!(finish_non_exhaustive)
+    } else {
+        quote!(finish)
+    };
+
+    match style {
+        FieldsStyle::Named => quote!(
+            #path { #pat_ts .. } => {
+                #let_ts
+                let mut __debug = #formatter.debug_struct(#ident_str);
+                #field_ts
+                __debug.#finish()
+            }
+        ),
+        FieldsStyle::Unnamed => quote!(
+            #path { #pat_ts .. } => {
+                #let_ts
+                let mut __debug = #formatter.debug_tuple(#ident_str);
                 #field_ts
-                .finish()
+                __debug.#finish()
+            }
+        ),
+        FieldsStyle::Unit => quote!(
+            #path => #formatter.write_str(#ident_str),
+        ),
+    }
+}
+
+// `match self { .. }` over the arms, for an enum without variants there is
+// no value to match a reference against
+pub(crate) fn impl_match(
+    variant_info_arr: &[VariantInfo],
+    arm_ts: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if variant_info_arr.is_empty() {
+        quote!(match *self {})
+    } else {
+        quote!(match self { #arm_ts })
+    }
+}
+
+// `if !Option::is_none(__self_1) { .. }` around the code that prints a field
+// with #[debug(skip_if = "...")]
+pub(crate) fn wrap_skip_if(
+    f: &FieldInfo,
+    field_ts: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match f.skip_if {
+        Some(ref skip_if) => {
+            let binding = &f.binding;
+            quote!(
+                if !#skip_if(#binding) {
+                    #field_ts
+                }
+            )
+        }
+        None => field_ts,
+    }
+}
+
+// `T: Trait` for every type parameter used by the types, and `T::Value: Trait`
+// for every associated type they use
+fn add_inferred_bounds(
+    generics: &mut syn::Generics,
+    ty_arr: &[&syn::Type],
+    trait_path: &syn::Path,
+) {
+    let generic_type_arr = generics
+        .type_params()
+        .map(|t| t.ident.to_string())
+        .collect::<Vec<_>>();
+
+    let mut generic_param_visiter = generic_param_visiter::GenericParamVisiter {
+        generic_type_arr: generic_type_arr.clone(),
+        generic_param_arr: vec![],
+    };
+
+    let mut associated_type_visiter = associated_type_visiter::AssociatedTypeVisiter {
+        generic_type_arr,
+        associated_type_arr: vec![],
+    };
+
+    for ty in ty_arr {
+        generic_param_visiter.visit_type(ty);
+        associated_type_visiter.visit_type(ty);
+    }
+
+    for g in generics.params.iter_mut() {
+        if let syn::GenericParam::Type(t) = g {
+            if generic_param_visiter.generic_param_arr.contains(&t.ident) {
+                t.bounds.push(parse_quote!(#trait_path));
             }
         }
+    }
+
+    let where_clause = generics.make_where_clause();
+    for associated_type in associated_type_visiter.associated_type_arr {
+        where_clause
+            .predicates
+            .push(parse_quote!(#associated_type: #trait_path));
+    }
+}
+
+// Name { 0: __self_0, .. } => ::core::fmt::Debug::fmt(__self_0, f),
+fn impl_debug_transparent_arm(
+    variant_info: &VariantInfo,
+    is_packed: bool,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let VariantInfo {
+        path,
+        field_info_arr,
+        ..
+    } = variant_info;
+
+    let f = match field_info_arr.as_slice() {
+        [f] => f,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                path,
+                "transparent requires exactly one field",
+            ))
+        }
+    };
+    if f.skip || f.skip_if.is_some() {
+        return Err(syn::Error::new_spanned(
+            &f.member,
+            "the only field of a transparent struct cannot be skipped",
+        ));
+    }
+
+    let formatter = formatter_ident();
+    let (pat_ts, let_ts) = bind_field_arr(field_info_arr, is_packed);
+    let value = field_value(f, field_info_arr);
+    Ok(quote!(
+        #path { #pat_ts .. } => {
+            #let_ts
+            ::core::fmt::Debug::fmt(#value, #formatter)
+        }
     ))
 }
 
-fn get_field_info_arr(ast: &syn::DeriveInput) -> syn::Result<Vec<FieldInfo>> {
-    if let syn::Data::Struct(syn::DataStruct {
-        fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
+// Binds every field to its `__self_N`, either in the pattern or, for a
+// #[repr(packed)] struct whose fields can't be borrowed, by copying it out:
+//
+//     Name { .. } => {
+//         let __self_0 = &{ self.x };
+//         ...
+//     }
+//
+// Skipped fields are only bound when a format string refers to them, so that
+// they don't have to be Copy.
+fn bind_field_arr(
+    field_info_arr: &[FieldInfo],
+    is_packed: bool,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    let mut pat_ts = proc_macro2::TokenStream::new();
+    let mut let_ts = proc_macro2::TokenStream::new();
+    for f in field_info_arr.iter() {
+        let member = &f.member;
+        let binding = &f.binding;
+        if !is_packed {
+            pat_ts.extend(quote!(#member: #binding,));
+            continue;
+        }
+
+        let is_referenced = field_info_arr
+            .iter()
+            .flat_map(|f| f.named_arg_arr.iter())
+            .any(|name| is_member_named(&f.member, name));
+        if !f.skip || is_referenced {
+            let_ts.extend(quote!(let #binding = &{ self.#member };));
+        }
+    }
+    (pat_ts, let_ts)
+}
+
+// Name { .. } => f.debug_struct("Name").finish_non_exhaustive(), or the
+// #[debug(with = "...")] given on the type
+fn impl_debug_opaque(
+    struct_attr: &StructAttr,
+    ident: &syn::Ident,
+    generics: &syn::Generics,
+) -> proc_macro2::TokenStream {
+    let mut generics = generics.clone();
+    if let Some(ref bound) = struct_attr.bound {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(bound.iter().cloned());
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let formatter = formatter_ident();
+    let body = match struct_attr.with {
+        Some(ref with) => quote!(#with(self, #formatter)),
+        None => {
+            let ident_str = match struct_attr.rename {
+                Some(ref rename) => rename.value(),
+                None => ident.to_string(),
+            };
+            quote!(#formatter.debug_struct(#ident_str).finish_non_exhaustive())
+        }
+    };
+
+    quote!(
+        impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #where_clause {
+            fn fmt(&self, #formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #body
+            }
+        }
+    )
+}
+
+// the expression passed to `.field(..)`, a reference to something Debug. At
+// most one of the options below is set, get_field_info rejects the rest.
+fn field_value(f: &FieldInfo, field_info_arr: &[FieldInfo]) -> proc_macro2::TokenStream {
+    let FieldInfo {
+        binding,
+        debug_attr,
+        named_arg_arr,
+        redact,
+        with,
+        max_len,
+        max_items,
         ..
-    }) = &ast.data
-    {
-        named.iter().map(|f| get_field_info(f)).collect()
+    } = f;
+
+    if let Some(ref redact) = redact {
+        quote!(&::core::format_args!("{}", #redact))
+    } else if let Some(ref with) = with {
+        quote!(&__DebugWith(#binding, #with))
+    } else if let Some(max_len) = max_len {
+        quote!(&#binding.__debug_max_len(#max_len))
+    } else if let Some(max_items) = max_items {
+        // `&'a [T]` has to reach __DebugMaxItems as `[T]`, since it is
+        // `&[T]` and not `&&[T]` that implements IntoIterator
+        let mut ty = &f.ty;
+        let mut deref_ts = proc_macro2::TokenStream::new();
+        while let syn::Type::Reference(syn::TypeReference { elem, .. }) = ty {
+            deref_ts.extend(quote!(*));
+            ty = elem;
+        }
+        quote!(&__DebugMaxItems(#deref_ts #binding, #max_items))
+    } else if let Some(ref debug_attr) = debug_attr {
+        let mut named_arg_ts = proc_macro2::TokenStream::new();
+        // the field's own ident is the argument name, `r#type = __self_1`
+        // is what `{type}` refers to
+        for f in field_info_arr.iter() {
+            if named_arg_arr
+                .iter()
+                .any(|name| is_member_named(&f.member, name))
+            {
+                let member = &f.member;
+                let named_binding = &f.binding;
+                named_arg_ts.extend(quote!(, #member = #named_binding));
+            }
+        }
+        quote!(&::core::format_args!(#debug_attr, #binding #named_arg_ts))
     } else {
-        Err(syn::Error::new_spanned(ast, "not found fields named"))
+        quote!(#binding)
     }
 }
 
-fn get_field_info(field: &syn::Field) -> syn::Result<FieldInfo> {
-    if let Some(ref ident) = field.ident {
-        let ident = ident.clone();
-        let mut debug_attr = None;
+// adapters for #[debug(max_len = N)] and #[debug(max_items = N)] that print
+// a prefix of the value followed by `… (N more)`
+fn impl_truncate_helper() -> proc_macro2::TokenStream {
+    quote!(
+        struct __DebugMaxItems<'a, T: ?Sized>(&'a T, usize);
 
-        for attr in field.attrs.iter() {
-            if attr.path().is_ident("debug") {
-                if let Ok(syn::MetaNameValue {
-                    value:
-                        syn::Expr::Lit(syn::ExprLit {
-                            lit: syn::Lit::Str(v),
-                            ..
-                        }),
-                    ..
-                }) = attr.meta.require_name_value()
-                {
-                    debug_attr = Some(v.value());
+        impl<'a, T: ?Sized> ::core::fmt::Debug for __DebugMaxItems<'a, T>
+        where
+            for<'b> &'b T: ::core::iter::IntoIterator,
+            for<'b> <&'b T as ::core::iter::IntoIterator>::Item: ::core::fmt::Debug,
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut list = f.debug_list();
+                let mut iter = ::core::iter::IntoIterator::into_iter(self.0);
+                list.entries(::core::iter::Iterator::take(&mut iter, self.1));
+                let rest = ::core::iter::Iterator::count(iter);
+                if rest > 0 {
+                    list.entry(&::core::format_args!("… ({} more)", rest));
                 }
-            } else {
-                return Err(syn::Error::new_spanned(attr, "except debug"));
+                list.finish()
             }
         }
-        Ok(FieldInfo { ident, debug_attr })
-    } else {
-        Err(syn::Error::new_spanned(field, "fail to get ident"))
+
+        // strings are cut after `max_len` chars, byte slices after `max_len`
+        // bytes
+        enum __DebugMaxLen<'a> {
+            Str(&'a str, usize),
+            Bytes(&'a [u8], usize),
+        }
+
+        impl<'a> ::core::fmt::Debug for __DebugMaxLen<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let (s, max_len) = match *self {
+                    __DebugMaxLen::Str(s, max_len) => (s, max_len),
+                    __DebugMaxLen::Bytes(b, max_len) => {
+                        return ::core::fmt::Debug::fmt(&__DebugMaxItems(b, max_len), f);
+                    }
+                };
+
+                let mut char_indices = s.char_indices();
+                match ::core::iter::Iterator::nth(&mut char_indices, max_len) {
+                    ::core::option::Option::Some((idx, _)) => {
+                        let rest = ::core::iter::Iterator::count(s[idx..].chars());
+                        ::core::fmt::Debug::fmt(&s[..idx], f)?;
+                        ::core::write!(f, "… ({} more)", rest)
+                    }
+                    ::core::option::Option::None => ::core::fmt::Debug::fmt(s, f),
+                }
+            }
+        }
+
+        // called with method syntax, so that String, Box<str>, Vec<u8>,
+        // [u8; N] and references to them reach these two impls by auto-deref
+        trait __DebugTruncate {
+            fn __debug_max_len(&self, max_len: usize) -> __DebugMaxLen<'_>;
+        }
+
+        impl __DebugTruncate for str {
+            fn __debug_max_len(&self, max_len: usize) -> __DebugMaxLen<'_> {
+                __DebugMaxLen::Str(self, max_len)
+            }
+        }
+
+        impl __DebugTruncate for [u8] {
+            fn __debug_max_len(&self, max_len: usize) -> __DebugMaxLen<'_> {
+                __DebugMaxLen::Bytes(self, max_len)
+            }
+        }
+    )
+}
+
+// the Formatter argument of the generated `fmt`, resolved at the macro's own
+// definition site so that user code like #[debug(with = "f")] never sees it
+pub(crate) fn formatter_ident() -> syn::Ident {
+    syn::Ident::new("f", proc_macro2::Span::mixed_site())
+}
+
+// whether `name` from a format string refers to the field, `type` is the
+// field `r#type`
+pub(crate) fn is_member_named(member: &syn::Member, name: &str) -> bool {
+    match member {
+        syn::Member::Named(ident) => ident.unraw() == name,
+        syn::Member::Unnamed(_) => false,
+    }
+}
+
+fn member_to_string(member: &syn::Member) -> String {
+    match member {
+        syn::Member::Named(ident) => ident.to_string(),
+        syn::Member::Unnamed(index) => index.index.to_string(),
     }
 }
 
-fn get_none_phantom_generic_param_arr(ast: &syn::DeriveInput) -> Vec<syn::Ident> {
-    let mut result = vec![];
+fn get_variant_info_arr(
+    ast: &syn::DeriveInput,
+    struct_attr: &StructAttr,
+) -> syn::Result<Vec<VariantInfo>> {
+    let ident = &ast.ident;
+    let mut variant_info_arr = match &ast.data {
+        syn::Data::Struct(syn::DataStruct { fields, .. }) => {
+            let ident_str = match struct_attr.rename {
+                Some(ref rename) => rename.value(),
+                None => ident.to_string(),
+            };
+            Ok(vec![get_variant_info(
+                parse_quote!(#ident),
+                ident_str,
+                fields,
+            )?])
+        }
+        syn::Data::Enum(syn::DataEnum { variants, .. }) => {
+            // the enum's own name never shows up in the output
+            if let Some(ref rename) = struct_attr.rename {
+                return Err(syn::Error::new_spanned(
+                    rename,
+                    "rename has no effect on an enum, rename its variants instead",
+                ));
+            }
 
-    if let syn::Data::Struct(syn::DataStruct {
-        fields: syn::Fields::Named(syn::FieldsNamed { named, .. }),
-        ..
-    }) = &ast.data
+            variants
+                .iter()
+                .map(|v| {
+                    let v_ident = &v.ident;
+                    let ident_str = match get_variant_rename(v)? {
+                        Some(rename) => rename.value(),
+                        None => v_ident.to_string(),
+                    };
+                    get_variant_info(parse_quote!(#ident::#v_ident), ident_str, &v.fields)
+                })
+                .collect()
+        }
+        // only CustomDebug prints unions, opaquely
+        syn::Data::Union(data_union) => Err(unsupported_union_error(data_union)),
+    }?;
+
+    if let Some(ref except_arr) = struct_attr.redact_all_except {
+        apply_redact_all_except(&mut variant_info_arr, except_arr)?;
+    }
+
+    Ok(variant_info_arr)
+}
+
+pub(crate) fn unsupported_union_error(data_union: &syn::DataUnion) -> syn::Error {
+    syn::Error::new_spanned(
+        data_union.union_token,
+        "unions are not supported, expected a struct or enum",
+    )
+}
+
+// redact every field not named in `redact_all_except(...)`, unless the field
+// already has its own skip or redact
+fn apply_redact_all_except(
+    variant_info_arr: &mut [VariantInfo],
+    except_arr: &[syn::Ident],
+) -> syn::Result<()> {
+    for except in except_arr {
+        let is_found = variant_info_arr
+            .iter()
+            .flat_map(|v| v.field_info_arr.iter())
+            .any(|f| f.member == syn::Member::Named(except.clone()));
+        if !is_found {
+            return Err(syn::Error::new_spanned(
+                except,
+                format!("no field named `{}`", except),
+            ));
+        }
+    }
+
+    for f in variant_info_arr
+        .iter_mut()
+        .flat_map(|v| v.field_info_arr.iter_mut())
     {
-        for field in named {
-            if let syn::Type::Path(syn::TypePath {
-                path: syn::Path { segments, .. },
-                ..
-            }) = &field.ty
-            {
-                if let Some(path_seg) = segments.last() {
-                    if path_seg.ident != "PhantomData" {
-                        let mut arguments = &path_seg.arguments;
-                        let mut target_ident = path_seg.ident.clone();
-                        loop {
-                            if let syn::PathArguments::AngleBracketed(
-                                syn::AngleBracketedGenericArguments { args, .. },
-                            ) = arguments
-                            {
-                                if let Some(&syn::GenericArgument::Type(syn::Type::Path(
-                                    syn::TypePath {
-                                        path: syn::Path { ref segments, .. },
-                                        ..
-                                    },
-                                ))) = args.first()
-                                {
-                                    if let Some(path_seg) = segments.last() {
-                                        arguments = &path_seg.arguments;
-                                        if arguments == &syn::PathArguments::None {
-                                            target_ident = path_seg.ident.clone();
-                                            break;
-                                        }
-                                    }
-                                }
-                            } else {
-                                break;
-                            }
-                        }
+        let is_except = match f.member {
+            syn::Member::Named(ref ident) => except_arr.contains(ident),
+            syn::Member::Unnamed(_) => false,
+        };
+        if !is_except && !f.skip && f.redact.is_none() {
+            f.redact = Some(REDACT_PLACEHOLDER.to_string());
+        }
+    }
 
-                        result.push(target_ident);
-                    }
-                }
+    Ok(())
+}
+
+fn get_variant_info(
+    path: syn::Path,
+    ident_str: String,
+    fields: &syn::Fields,
+) -> syn::Result<VariantInfo> {
+    let style = match fields {
+        syn::Fields::Named(_) => FieldsStyle::Named,
+        syn::Fields::Unnamed(_) => FieldsStyle::Unnamed,
+        syn::Fields::Unit => FieldsStyle::Unit,
+    };
+
+    let field_info_arr = fields
+        .iter()
+        .enumerate()
+        .map(|(idx, f)| get_field_info(f, idx))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    // `{name}` in a format string has to be another field of the same variant
+    for f in field_info_arr.iter() {
+        for name in f.named_arg_arr.iter() {
+            let is_found = field_info_arr
+                .iter()
+                .any(|f| is_member_named(&f.member, name));
+            if !is_found {
+                return Err(syn::Error::new_spanned(
+                    &f.debug_attr,
+                    format!("no field named `{}` to use in the format string", name),
+                ));
             }
         }
     }
-    result
+
+    // debug_tuple prints no field names, so there is nothing to rename
+    if style == FieldsStyle::Unnamed {
+        if let Some(rename) = field_info_arr.iter().find_map(|f| f.rename.as_ref()) {
+            return Err(syn::Error::new_spanned(
+                rename,
+                "rename has no effect on a tuple field",
+            ));
+        }
+    }
+
+    Ok(VariantInfo {
+        path,
+        ident_str,
+        style,
+        field_info_arr,
+    })
 }
 
-fn get_struct_escape_hatch(ast: &DeriveInput) -> Option<String> {
+// #[debug(rename = "...")] on an enum variant
+fn get_variant_rename(variant: &syn::Variant) -> syn::Result<Option<syn::LitStr>> {
     let mut result = None;
+    for attr in variant.attrs.iter() {
+        if attr.path().is_ident("debug") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    result = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `rename`"))
+                }
+            })?;
+        }
+    }
+    Ok(result)
+}
+
+fn get_field_info(field: &syn::Field, idx: usize) -> syn::Result<FieldInfo> {
+    let member = match field.ident {
+        Some(ref ident) => syn::Member::Named(ident.clone()),
+        None => syn::Member::Unnamed(idx.into()),
+    };
+    let binding = format_ident!("__self_{}", idx);
+    let mut debug_attr = None;
+    let mut skip = false;
+    let mut skip_if = None;
+    let mut redact = None;
+    let mut with = None;
+    let mut rename = None;
+    let mut max_len = None;
+    let mut max_items = None;
+    let mut bound = None;
+    // fmt, redact, with, max_len and max_items each decide how the value is
+    // printed, so at most one of them can be given
+    let mut format_option_arr: Vec<syn::Path> = vec![];
+
+    for attr in field.attrs.iter() {
+        if !attr.path().is_ident("debug") {
+            // doc comments, #[serde(...)] and friends belong to someone else
+            continue;
+        }
+
+        match attr.meta {
+            // #[debug = "0b{:08b}"]
+            syn::Meta::NameValue(syn::MetaNameValue { ref value, .. }) => {
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(v),
+                    ..
+                }) = value
+                {
+                    debug_attr = Some(v.clone());
+                    // the same as `fmt = "..."`
+                    format_option_arr.push(syn::Ident::new("fmt", v.span()).into());
+                } else {
+                    return Err(syn::Error::new_spanned(value, "expected string literal"));
+                }
+            }
+            // #[debug(fmt = "0b{:08b}")]
+            // #[debug(skip)]
+            // #[debug(skip_if = "Option::is_none")]
+            // #[debug(redact)]
+            // #[debug(redact = "<hidden>")]
+            // #[debug(with = "path::to::fn")]
+            // #[debug(rename = "...")]
+            // #[debug(max_len = 64)]
+            // #[debug(max_items = 10)]
+            // #[debug(bound = "T: Debug")]
+            syn::Meta::List(_) => {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("fmt") {
+                        debug_attr = Some(meta.value()?.parse()?);
+                        format_option_arr.push(meta.path.clone());
+                        Ok(())
+                    } else if meta.path.is_ident("skip") {
+                        skip = true;
+                        Ok(())
+                    } else if meta.path.is_ident("skip_if") {
+                        let s: syn::LitStr = meta.value()?.parse()?;
+                        skip_if = Some(s.parse()?);
+                        Ok(())
+                    } else if meta.path.is_ident("redact") {
+                        if meta.input.peek(syn::Token![=]) {
+                            let s: syn::LitStr = meta.value()?.parse()?;
+                            redact = Some(s.value());
+                        } else {
+                            redact = Some(REDACT_PLACEHOLDER.to_string());
+                        }
+                        format_option_arr.push(meta.path.clone());
+                        Ok(())
+                    } else if meta.path.is_ident("with") {
+                        let s: syn::LitStr = meta.value()?.parse()?;
+                        with = Some(s.parse()?);
+                        format_option_arr.push(meta.path.clone());
+                        Ok(())
+                    } else if meta.path.is_ident("rename") {
+                        rename = Some(meta.value()?.parse()?);
+                        Ok(())
+                    } else if meta.path.is_ident("max_len") {
+                        let n: syn::LitInt = meta.value()?.parse()?;
+                        max_len = Some(n.base10_parse::<usize>()?);
+                        format_option_arr.push(meta.path.clone());
+                        Ok(())
+                    } else if meta.path.is_ident("max_items") {
+                        let n: syn::LitInt = meta.value()?.parse()?;
+                        max_items = Some(n.base10_parse::<usize>()?);
+                        format_option_arr.push(meta.path.clone());
+                        Ok(())
+                    } else if meta.path.is_ident("bound") {
+                        let s: syn::LitStr = meta.value()?.parse()?;
+                        bound = Some(s.parse_with(Punctuated::parse_terminated)?);
+                        Ok(())
+                    } else {
+                        Err(meta
+                            .error("expected `fmt`, `skip`, `skip_if`, `redact`, `with`, `rename`, `max_len`, `max_items` or `bound`"))
+                    }
+                })?;
+            }
+            syn::Meta::Path(_) => {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    r#"expected `#[debug = "..."]` or `#[debug(...)]`"#,
+                ));
+            }
+        }
+    }
+    if let [first, second, ..] = format_option_arr.as_slice() {
+        let (first, second) = (first.to_token_stream(), second.to_token_stream());
+        let msg = if first.to_string() == second.to_string() {
+            format!("`{}` is given more than once", second)
+        } else {
+            format!(
+                "`{}` can't be combined with `{}` on the same field",
+                second, first
+            )
+        };
+        return Err(syn::Error::new_spanned(second, msg));
+    }
+
+    let (named_arg_arr, format_trait_arr) = match debug_attr {
+        Some(ref debug_attr) => {
+            let debug_format = format_spec::get_debug_format(debug_attr)?;
+            (debug_format.named_arg_arr, debug_format.format_trait_arr)
+        }
+        None => (vec![], vec![]),
+    };
+
+    Ok(FieldInfo {
+        member,
+        ty: field.ty.clone(),
+        binding,
+        debug_attr,
+        named_arg_arr,
+        format_trait_arr,
+        skip,
+        skip_if,
+        redact,
+        with,
+        rename,
+        max_len,
+        max_items,
+        bound,
+    })
+}
+
+// whether any of the type parameters shows up somewhere in `ty`
+pub(crate) fn is_generic_type(ty: &syn::Type, generic_type_arr: &[String]) -> bool {
+    fn walk(ts: proc_macro2::TokenStream, generic_type_arr: &[String]) -> bool {
+        ts.into_iter().any(|tt| match tt {
+            proc_macro2::TokenTree::Ident(ident) => generic_type_arr.contains(&ident.to_string()),
+            proc_macro2::TokenTree::Group(group) => walk(group.stream(), generic_type_arr),
+            _ => false,
+        })
+    }
+
+    walk(quote!(#ty), generic_type_arr)
+}
+
+fn get_struct_attr(ast: &DeriveInput) -> syn::Result<StructAttr> {
+    let mut result = StructAttr::default();
+    // options about how the fields are printed, which do nothing when no
+    // field is printed
+    let mut field_option_arr: Vec<syn::Path> = vec![];
     for attr in ast.attrs.iter() {
+        // #[repr(packed)], #[repr(C, packed(2))]
+        if attr.path().is_ident("repr") {
+            if let syn::Meta::List(ref list) = attr.meta {
+                result.packed |= list.tokens.clone().into_iter().any(|tt| {
+                    matches!(tt, proc_macro2::TokenTree::Ident(ref ident) if ident == "packed")
+                });
+            }
+        }
+
         if attr.path().is_ident("debug") {
-            let res = attr.parse_nested_meta(|meta| {
+            attr.parse_nested_meta(|meta| {
                 if meta.path.is_ident("bound") {
                     let value = meta.value()?;
-                    // this parses `"EarlGrey"`
+                    // this parses `"T::Value: Debug, U: Debug"`, reporting
+                    // errors on the string literal
                     let s: syn::LitStr = value.parse()?;
-                    result = Some(s.value());
+                    result.bound = Some(s.parse_with(Punctuated::parse_terminated)?);
+
+                    Ok(())
+                } else if meta.path.is_ident("redact_all_except") {
+                    let mut except_arr = vec![];
+                    meta.parse_nested_meta(|meta| {
+                        except_arr.push(meta.path.require_ident()?.clone());
+                        Ok(())
+                    })?;
+                    result.redact_all_except = Some(except_arr);
+                    field_option_arr.push(meta.path.clone());
+
+                    Ok(())
+                } else if meta.path.is_ident("rename") {
+                    result.rename = Some(meta.value()?.parse()?);
+
+                    Ok(())
+                } else if meta.path.is_ident("compact") {
+                    result.compact = true;
+                    field_option_arr.push(meta.path.clone());
+
+                    Ok(())
+                } else if meta.path.is_ident("transparent") {
+                    if !matches!(ast.data, syn::Data::Struct(_)) {
+                        return Err(meta.error("transparent is only supported on structs"));
+                    }
+                    result.transparent = true;
+                    field_option_arr.push(meta.path.clone());
+
+                    Ok(())
+                } else if meta.path.is_ident("with") {
+                    let s: syn::LitStr = meta.value()?.parse()?;
+                    result.with = Some(s.parse()?);
+
+                    Ok(())
+                } else if meta.path.is_ident("bounds") {
+                    let s: syn::LitStr = meta.value()?.parse()?;
+                    result.bounds = match s.value().as_str() {
+                        "params" => BoundsStrategy::Params,
+                        "fields" => BoundsStrategy::Fields,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                s,
+                                r#"expected `bounds = "params"` or `bounds = "fields"`"#,
+                            ))
+                        }
+                    };
+                    field_option_arr.push(meta.path.clone());
 
                     Ok(())
                 } else {
-                    Err(syn::Error::new_spanned(attr, "ignore"))
+                    Err(meta.error(
+                        "expected `bound`, `bounds`, `compact`, `redact_all_except`, `rename`, `transparent` or `with`",
+                    ))
                 }
-            });
+            })?;
+        }
+    }
 
-            if let Ok(_) = res {
-                break;
-            }
+    if let Some(path) = field_option_arr.first() {
+        let name = path.to_token_stream();
+        if result.with.is_some() {
+            return Err(syn::Error::new_spanned(
+                path,
+                format!(
+                    "`{}` has no effect when `with` formats the whole value",
+                    name
+                ),
+            ));
+        }
+        if matches!(ast.data, syn::Data::Union(_)) {
+            return Err(syn::Error::new_spanned(
+                path,
+                format!(
+                    "`{}` has no effect on a union, its fields are never printed",
+                    name
+                ),
+            ));
         }
     }
-    result
+
+    Ok(result)
 }
@@ -0,0 +1,55 @@
+// Enums are formatted the same way the standard library's derive formats
+// them: each variant prints under its own name, using debug_struct for
+// variants with named fields, debug_tuple for tuple variants, and just the
+// name for unit variants.
+//
+// Field attributes like `#[debug = "..."]` apply inside variants the same as
+// on struct fields, and bounds are inferred from the field types of every
+// variant.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[derive(CustomDebug)]
+pub enum Shape<T> {
+    Circle {
+        radius: T,
+        #[debug = "0b{:08b}"]
+        flags: u8,
+    },
+    Line(T, #[debug = "{}px"] u32),
+    Empty,
+}
+
+#[derive(CustomDebug)]
+pub enum Marker<T> {
+    Tag(PhantomData<T>),
+}
+
+#[derive(CustomDebug)]
+pub enum Never {}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    let circle = Shape::Circle {
+        radius: 1.5,
+        flags: 0b101,
+    };
+    assert_eq!(
+        format!("{:?}", circle),
+        "Circle { radius: 1.5, flags: 0b00000101 }"
+    );
+
+    let line = Shape::Line("a", 3);
+    assert_eq!(format!("{:?}", line), r#"Line("a", 3px)"#);
+
+    let empty: Shape<u8> = Shape::Empty;
+    assert_eq!(format!("{:?}", empty), "Empty");
+    assert_eq!(format!("{:#?}", empty), "Empty");
+
+    struct NotDebug;
+    assert_debug::<Marker<NotDebug>>();
+    assert_debug::<Never>();
+}
@@ -0,0 +1,32 @@
+// Tuple structs print through `debug_tuple` and unit structs print just their
+// name, matching the standard library's derive. `#[debug = "..."]` works on
+// tuple fields too, and bounds are inferred the same way as for named fields,
+// so newtype wrappers can use the derive.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[derive(CustomDebug)]
+pub struct Meters(#[debug = "{}m"] f64);
+
+#[derive(CustomDebug)]
+pub struct Wrapper<T>(T, u8);
+
+#[derive(CustomDebug)]
+pub struct Tagged<T>(PhantomData<T>, &'static str);
+
+#[derive(CustomDebug)]
+pub struct Marker;
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    assert_eq!(format!("{:?}", Meters(2.5)), "Meters(2.5m)");
+    assert_eq!(format!("{:?}", Wrapper("a", 1)), r#"Wrapper("a", 1)"#);
+    assert_eq!(format!("{:#?}", Wrapper(1, 2)), "Wrapper(\n    1,\n    2,\n)");
+    assert_eq!(format!("{:?}", Marker), "Marker");
+
+    struct NotDebug;
+    assert_debug::<Tagged<NotDebug>>();
+}
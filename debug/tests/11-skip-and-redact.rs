@@ -0,0 +1,66 @@
+// Some fields should never end up in logs, either because they hold secrets
+// or because they are too large to be useful.
+//
+// `#[debug(skip)]` leaves a field out entirely. The output ends with `..` so
+// that readers can tell something was omitted, the same as
+// `DebugStruct::finish_non_exhaustive`.
+//
+// `#[debug(redact)]` keeps the field name but prints `***` in place of its
+// value, and `#[debug(redact = "...")]` uses the given placeholder instead.
+//
+// On the struct, `#[debug(redact_all_except(a, b))]` redacts every field
+// other than the ones listed.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Login {
+    user: &'static str,
+    #[debug(redact)]
+    password: &'static str,
+    #[debug(redact = "<token>")]
+    token: &'static str,
+    #[debug(skip)]
+    buffer: Vec<u8>,
+}
+
+#[derive(CustomDebug)]
+#[debug(redact_all_except(id, kind))]
+pub struct Record {
+    id: u32,
+    kind: &'static str,
+    email: &'static str,
+    #[debug(redact = "?")]
+    phone: &'static str,
+    #[debug(skip)]
+    notes: &'static str,
+}
+
+#[derive(CustomDebug)]
+pub struct Handle(u32, #[debug(skip)] Vec<u8>);
+
+fn main() {
+    let login = Login {
+        user: "root",
+        password: "hunter2",
+        token: "abc",
+        buffer: vec![0; 1024],
+    };
+    let debug = format!("{:?}", login);
+    let expected = r#"Login { user: "root", password: ***, token: <token>, .. }"#;
+    assert_eq!(debug, expected);
+
+    let record = Record {
+        id: 7,
+        kind: "user",
+        email: "a@b.c",
+        phone: "123",
+        notes: "",
+    };
+    let debug = format!("{:?}", record);
+    let expected = r#"Record { id: 7, kind: "user", email: ***, phone: ?, .. }"#;
+    assert_eq!(debug, expected);
+
+    let debug = format!("{:?}", Handle(3, vec![1, 2]));
+    assert_eq!(debug, "Handle(3, ..)");
+}
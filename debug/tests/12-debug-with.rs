@@ -0,0 +1,55 @@
+// A format string is not always enough. `#[debug(with = "path::to::fn")]`
+// formats the field by calling a function with the signature
+//
+//     fn(&T, &mut std::fmt::Formatter) -> std::fmt::Result
+//
+// where T is the field's type. This covers things like printing bytes as hex
+// or durations in a human-friendly way without introducing wrapper newtypes.
+//
+// The function may be generic as long as its type parameters can be inferred
+// from the field type.
+
+use derive_debug::CustomDebug;
+use std::fmt;
+use std::time::Duration;
+
+mod fmt_util {
+    use std::fmt;
+
+    pub fn hex<T: AsRef<[u8]>>(bytes: &T, f: &mut fmt::Formatter) -> fmt::Result {
+        for b in bytes.as_ref() {
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+fn millis(d: &Duration, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}ms", d.as_millis())
+}
+
+#[derive(CustomDebug)]
+pub struct Packet {
+    #[debug(with = "fmt_util::hex")]
+    payload: Vec<u8>,
+    #[debug(with = "millis")]
+    elapsed: Duration,
+}
+
+#[derive(CustomDebug)]
+pub enum Digest {
+    Sha(#[debug(with = "fmt_util::hex")] [u8; 4]),
+}
+
+fn main() {
+    let packet = Packet {
+        payload: vec![0xde, 0xad, 0xbe, 0xef],
+        elapsed: Duration::from_millis(1500),
+    };
+    let debug = format!("{:?}", packet);
+    let expected = "Packet { payload: deadbeef, elapsed: 1500ms }";
+    assert_eq!(debug, expected);
+
+    let debug = format!("{:?}", Digest::Sha([1, 2, 3, 4]));
+    assert_eq!(debug, "Sha(01020304)");
+}
@@ -0,0 +1,44 @@
+// `#[debug(rename = "...")]` changes the name printed for a field, a struct,
+// or an enum variant, without touching the Rust identifier. This is useful
+// for types generated by other macros whose identifiers are mangled.
+//
+// Tuple fields have no printed name and the enum's own name is never printed,
+// so renaming either of those is an error.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(rename = "Config")]
+pub struct __GeneratedConfig0 {
+    #[debug(rename = "timeout")]
+    __field_timeout: u32,
+    retries: u8,
+}
+
+#[derive(CustomDebug)]
+#[debug(rename = "Id")]
+pub struct __Id0(u32);
+
+#[derive(CustomDebug)]
+pub enum Event {
+    #[debug(rename = "Click")]
+    __Click0 {
+        #[debug(rename = "x")]
+        __x: i32,
+    },
+    #[debug(rename = "Close")]
+    __Close0,
+}
+
+fn main() {
+    let config = __GeneratedConfig0 {
+        __field_timeout: 30,
+        retries: 3,
+    };
+    let debug = format!("{:?}", config);
+    assert_eq!(debug, "Config { timeout: 30, retries: 3 }");
+
+    assert_eq!(format!("{:?}", __Id0(5)), "Id(5)");
+    assert_eq!(format!("{:?}", Event::__Click0 { __x: -1 }), "Click { x: -1 }");
+    assert_eq!(format!("{:?}", Event::__Close0), "Close");
+}
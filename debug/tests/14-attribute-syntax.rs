@@ -0,0 +1,57 @@
+// The format string can be written either as `#[debug = "..."]` or as
+// `#[debug(fmt = "...")]`, and the list form can be combined with the other
+// field options.
+//
+// Attributes that belong to something else, like doc comments or lint
+// attributes, are left alone. Malformed `debug` attributes are reported as
+// errors instead of being silently dropped.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+pub trait Trait {
+    type Value;
+    type Other;
+}
+
+/// A field with several attributes.
+#[derive(CustomDebug)]
+#[allow(dead_code)]
+pub struct Field {
+    /// The display name.
+    name: &'static str,
+    #[debug = "0b{:08b}"]
+    bitmask: u8,
+    #[allow(unused)]
+    #[debug(fmt = "{:#x}", rename = "address")]
+    addr: u32,
+}
+
+#[derive(CustomDebug)]
+#[debug(bound = "T::Value: Debug, T::Other: Debug")]
+pub struct Wrapper<T: Trait> {
+    value: T::Value,
+    other: Vec<T::Other>,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    let f = Field {
+        name: "F",
+        bitmask: 0b00011100,
+        addr: 255,
+    };
+    let debug = format!("{:?}", f);
+    let expected = r#"Field { name: "F", bitmask: 0b00011100, address: 0xff }"#;
+    assert_eq!(debug, expected);
+
+    struct Id;
+
+    impl Trait for Id {
+        type Value = u8;
+        type Other = i8;
+    }
+
+    assert_debug::<Wrapper<Id>>();
+}
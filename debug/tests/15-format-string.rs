@@ -0,0 +1,113 @@
+// The format string in `#[debug = "..."]` is checked while expanding the
+// macro. It has to contain exactly one `{}` placeholder for the field value,
+// and mistakes like an unknown format trait are reported on the string literal
+// instead of inside the generated code.
+//
+// Other fields of the same struct can be referred to by name, either as a
+// value or as a width or precision:
+//
+//     #[debug = "{}/{capacity}"]
+//     len: usize,
+//     capacity: usize,
+//
+// Bounds follow the format string: `{:x}` on a `T` field needs `T: LowerHex`
+// rather than Debug, and a field referenced as `{total}` needs Display even
+// when it is skipped itself.
+//
+// A field with a raw name like `r#type` is referred to without the `r#`, as
+// `{type}`, the same as in `format!`.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Buffer {
+    #[debug = "{}/{capacity}"]
+    len: usize,
+    capacity: usize,
+    #[debug = "{:.digits$} ({digits} digits)"]
+    ratio: f64,
+    #[debug(skip)]
+    digits: usize,
+    #[debug = "{{{:>width$}}}"]
+    tag: &'static str,
+    #[debug(skip)]
+    width: usize,
+}
+
+#[derive(CustomDebug)]
+pub enum Shape {
+    Rect {
+        #[debug = "{}x{height}"]
+        width: u32,
+        height: u32,
+    },
+    Circle(#[debug = "r={0:.1}"] f32),
+}
+
+#[derive(CustomDebug)]
+pub struct Page<T, U> {
+    #[debug = "0x{:x}"]
+    value: T,
+    #[debug = "{:?} of {total}"]
+    index: u32,
+    #[debug(skip)]
+    total: U,
+}
+
+// implements LowerHex and Display, but not Debug
+pub struct Opaque(u8);
+
+impl std::fmt::LowerHex for Opaque {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl std::fmt::Display for Opaque {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[derive(CustomDebug)]
+pub struct Token {
+    #[debug = "{} ({type})"]
+    text: &'static str,
+    #[debug(skip)]
+    r#type: u8,
+}
+
+fn main() {
+    let buffer = Buffer {
+        len: 3,
+        capacity: 8,
+        ratio: 0.375,
+        digits: 2,
+        tag: "ab",
+        width: 4,
+    };
+    let debug = format!("{:?}", buffer);
+    let expected =
+        "Buffer { len: 3/8, capacity: 8, ratio: 0.38 (2 digits), tag: {  ab}, .. }";
+    assert_eq!(debug, expected);
+
+    let rect = Shape::Rect {
+        width: 3,
+        height: 4,
+    };
+    assert_eq!(format!("{:?}", rect), "Rect { width: 3x4, height: 4 }");
+    assert_eq!(format!("{:?}", Shape::Circle(1.25)), "Circle(r=1.2)");
+
+    let page = Page {
+        value: Opaque(255),
+        index: 2,
+        total: Opaque(9),
+    };
+    assert_eq!(format!("{:?}", page), "Page { value: 0xff, index: 2 of 9, .. }");
+
+    let token = Token {
+        text: "fn",
+        r#type: 3,
+    };
+    assert_eq!(format!("{:?}", token), "Token { text: fn (3), .. }");
+}
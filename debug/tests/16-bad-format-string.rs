@@ -0,0 +1,30 @@
+// Mistakes in a `#[debug = "..."]` format string are reported on the string
+// literal while expanding the macro.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct UnknownTrait {
+    #[debug = "0b{:08q}"]
+    bitmask: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct TwoPlaceholders {
+    #[debug = "{} and {}"]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct UnknownField {
+    #[debug = "{}/{capacity}"]
+    len: usize,
+}
+
+#[derive(CustomDebug)]
+pub struct Unterminated {
+    #[debug = "{:?"]
+    value: u8,
+}
+
+fn main() {}
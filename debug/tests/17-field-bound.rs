@@ -0,0 +1,75 @@
+// `#[debug(bound = "...")]` on a field replaces only the bounds that would
+// have been inferred from that field's type. Bounds inferred from the other
+// fields stay in place. `#[debug(bound = "")]` means the field needs no
+// bounds at all.
+//
+//     impl<T: Trait, U: Debug> Debug for Wrapper<T, U>
+//     where
+//         T::Value: Debug,
+//     {...}
+//
+// Fields that are not printed through their Debug impl, because they are
+// skipped, redacted, or formatted with `#[debug(with = "...")]`, don't add
+// inferred bounds either.
+
+use derive_debug::CustomDebug;
+use std::fmt::{self, Debug};
+
+pub trait Trait {
+    type Value;
+}
+
+#[derive(CustomDebug)]
+pub struct Wrapper<T: Trait, U> {
+    #[debug(bound = "T::Value: Debug")]
+    field: Field<T>,
+    normal: U,
+}
+
+#[derive(CustomDebug)]
+struct Field<T: Trait> {
+    values: Vec<T::Value>,
+}
+
+fn opaque<T>(_: &T, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("?")
+}
+
+#[derive(CustomDebug)]
+pub struct Unbounded<A, B, C, D> {
+    #[debug(bound = "")]
+    #[debug(with = "opaque")]
+    a: A,
+    #[debug(skip)]
+    b: B,
+    #[debug(redact)]
+    c: C,
+    #[debug(with = "opaque")]
+    d: D,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    struct Id;
+
+    impl Trait for Id {
+        type Value = u8;
+    }
+
+    assert_debug::<Wrapper<Id, String>>();
+
+    struct NotDebug;
+
+    assert_debug::<Unbounded<NotDebug, NotDebug, NotDebug, NotDebug>>();
+    let unbounded = Unbounded {
+        a: NotDebug,
+        b: NotDebug,
+        c: NotDebug,
+        d: NotDebug,
+    };
+    assert_eq!(
+        format!("{:?}", unbounded),
+        "Unbounded { a: ?, c: ***, d: ?, .. }"
+    );
+}
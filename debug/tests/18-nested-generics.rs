@@ -0,0 +1,62 @@
+// Bounds are inferred from every place a type parameter appears in a field
+// type, not only the first generic argument of a path. Maps, tuples, arrays,
+// references and nested containers all contribute.
+//
+// PhantomData contributes nothing no matter how deeply it is nested, and
+// neither do fn pointers, whose Debug impl doesn't depend on their argument
+// types. The same goes for associated types like `T::Value` inside them.
+//
+//     impl<'a, K: Debug, V: Debug, T: Debug, U: Debug, M, F, const N: usize>
+//         Debug for Field<'a, K, V, T, U, M, F, N>
+//     {...}
+
+use derive_debug::CustomDebug;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+#[derive(CustomDebug)]
+pub struct Field<'a, K, V, T, U, M, F, const N: usize> {
+    map: HashMap<K, Vec<V>>,
+    pair: (Option<T>, u8),
+    array: [&'a U; N],
+    marker: Option<PhantomData<M>>,
+    callback: fn(F) -> F,
+}
+
+pub trait Trait {
+    type Value;
+}
+
+#[derive(CustomDebug)]
+pub struct Projected<T: Trait> {
+    marker: PhantomData<T::Value>,
+    nested: Option<PhantomData<<T as Trait>::Value>>,
+    callback: fn(T::Value) -> T::Value,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    struct NotDebug;
+
+    assert_debug::<Field<u8, String, bool, char, NotDebug, NotDebug, 2>>();
+
+    struct NotDebugValue;
+    impl Trait for NotDebugValue {
+        type Value = NotDebug;
+    }
+    assert_debug::<Projected<NotDebugValue>>();
+
+    let value = 'x';
+    let field: Field<u8, u8, u8, char, NotDebug, NotDebug, 1> = Field {
+        map: HashMap::new(),
+        pair: (Some(1), 2),
+        array: [&value],
+        marker: None,
+        callback: |f| f,
+    };
+    let debug = format!("{:?}", field);
+    let expected = "Field { map: {}, pair: (Some(1), 2), array: ['x'], marker: None, callback: 0x";
+    assert!(debug.starts_with(expected));
+}
@@ -0,0 +1,62 @@
+// Instead of bounding type parameters, `#[debug(bounds = "fields")]` puts a
+// bound on every field type that involves a type parameter:
+//
+//     impl<T: Trait> Debug for Field<T>
+//     where
+//         Rc<T::Value>: Debug,
+//         Vec<<T as Trait>::Other>: Debug,
+//     {...}
+//
+// This handles associated types, smart pointers and anything else uniformly,
+// because the compiler works out what the field type needs. Like any bound
+// that mentions the type itself, it doesn't work for recursive types such as
+// the ones in 06-bound-trouble.rs, which is why it is opt-in.
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+pub trait Trait {
+    type Value;
+    type Other;
+}
+
+#[derive(CustomDebug)]
+#[debug(bounds = "fields")]
+pub struct Field<T: Trait> {
+    value: Rc<T::Value>,
+    other: Vec<<T as Trait>::Other>,
+    name: &'static str,
+    marker: PhantomData<T>,
+}
+
+#[derive(CustomDebug)]
+#[debug(bounds = "params")]
+pub struct Plain<T> {
+    value: T,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    struct Id;
+
+    impl Trait for Id {
+        type Value = u8;
+        type Other = bool;
+    }
+
+    assert_debug::<Field<Id>>();
+    assert_debug::<Plain<u8>>();
+
+    let field: Field<Id> = Field {
+        value: Rc::new(1),
+        other: vec![true],
+        name: "f",
+        marker: PhantomData,
+    };
+    let debug = format!("{:?}", field);
+    let expected = r#"Field { value: 1, other: [true], name: "f", marker: PhantomData<"#;
+    assert!(debug.starts_with(expected));
+}
@@ -0,0 +1,49 @@
+// Associated types can also be written as qualified paths, possibly nested
+// inside other types or projecting out of another associated type. Each
+// distinct one gets a single `Debug` bound no matter how many fields use it.
+//
+//     impl<T: Trait, I: Iterator> Debug for Field<T, I>
+//     where
+//         <T as Trait>::Value: Debug,
+//         <I as Iterator>::Item: Debug,
+//         <T::Inner as Trait>::Value: Debug,
+//     {...}
+
+use derive_debug::CustomDebug;
+use std::fmt::Debug;
+
+pub trait Trait {
+    type Value;
+    type Inner: Trait;
+}
+
+#[derive(CustomDebug)]
+pub struct Field<T: Trait, I: Iterator> {
+    value: <T as Trait>::Value,
+    values: Vec<<T as Trait>::Value>,
+    items: Vec<<I as Iterator>::Item>,
+    nested: Box<<T::Inner as Trait>::Value>,
+}
+
+fn assert_debug<F: Debug>() {}
+
+fn main() {
+    // Neither of these implements Debug, only their associated types do.
+    struct Id;
+    struct Iter;
+
+    impl Trait for Id {
+        type Value = u8;
+        type Inner = Id;
+    }
+
+    impl Iterator for Iter {
+        type Item = bool;
+
+        fn next(&mut self) -> Option<bool> {
+            None
+        }
+    }
+
+    assert_debug::<Field<Id, Iter>>();
+}
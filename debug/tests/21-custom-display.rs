@@ -0,0 +1,99 @@
+// CustomDisplay implements Display from a template on the struct, or on each
+// variant of an enum. Fields are referred to by name, tuple fields by index.
+//
+//     #[derive(CustomDisplay)]
+//     #[display("{name} <{email}>")]
+//     pub struct User {
+//         name: String,
+//         email: String,
+//     }
+//
+// Bounds are inferred the same way as for CustomDebug, using the trait each
+// field is formatted with: `{value}` needs `T: Display`, `{value:?}` needs
+// `T: Debug`. Fields the template doesn't use need no bound at all.
+
+use derive_debug::{CustomDebug, CustomDisplay};
+
+#[derive(CustomDebug, CustomDisplay)]
+#[display("{name} <{email}>")]
+pub struct User {
+    name: String,
+    #[debug(redact)]
+    email: String,
+}
+
+#[derive(CustomDisplay)]
+#[display("({0}, {1:.2})")]
+pub struct Point(i32, f64);
+
+#[derive(CustomDisplay)]
+pub enum Shape {
+    #[display("circle of radius {radius}")]
+    Circle { radius: u32 },
+    #[display("{}x{}")]
+    Rect(u32, u32),
+    #[display("{{empty}}")]
+    Empty,
+}
+
+#[derive(CustomDisplay)]
+#[display("{{{:>1$}}}")]
+pub struct Padded(&'static str, usize);
+
+#[derive(CustomDisplay)]
+#[display("{label}={value:?}")]
+pub struct Labeled<T, U> {
+    label: &'static str,
+    value: T,
+    #[allow(dead_code)]
+    unused: U,
+}
+
+// the enum's template is used by variants without their own
+#[derive(CustomDisplay)]
+#[display("a level")]
+pub enum Level {
+    Low,
+    #[display("high!")]
+    High,
+}
+
+struct NotDebug;
+
+fn assert_display<T: std::fmt::Display>() {}
+
+fn main() {
+    let user = User {
+        name: "Ferris".to_owned(),
+        email: "ferris@example.com".to_owned(),
+    };
+    assert_eq!(user.to_string(), "Ferris <ferris@example.com>");
+    assert_eq!(
+        format!("{:?}", user),
+        r#"User { name: "Ferris", email: *** }"#
+    );
+
+    assert_eq!(Point(1, 2.0).to_string(), "(1, 2.00)");
+
+    assert_eq!(
+        Shape::Circle { radius: 3 }.to_string(),
+        "circle of radius 3"
+    );
+    assert_eq!(Shape::Rect(4, 5).to_string(), "4x5");
+    assert_eq!(Shape::Empty.to_string(), "{empty}");
+
+    assert_eq!(Padded("ab", 4).to_string(), "{  ab}");
+    assert_eq!(
+        Labeled {
+            label: "x",
+            value: vec![1, 2],
+            unused: NotDebug,
+        }
+        .to_string(),
+        "x=[1, 2]"
+    );
+    assert_display::<Labeled<Vec<u8>, NotDebug>>();
+
+    assert_eq!(Level::Low.to_string(), "a level");
+    assert_eq!(Level::High.to_string(), "high!");
+}
@@ -0,0 +1,54 @@
+// `#[debug(compact)]` keeps the output on one line even when pretty printing
+// with `{:#?}`, which suits small types nested inside bigger ones.
+//
+// `#[debug(transparent)]` is for newtypes, it prints only the Debug output of
+// the single field, so `UserId(7)` shows up as `7`. Field attributes like
+// `#[debug = "..."]` still apply to that field.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(compact)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct UserId(u64);
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Mask {
+    #[debug = "0b{:04b}"]
+    bits: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct Shape {
+    origin: Point,
+    owner: UserId,
+    mask: Mask,
+}
+
+fn main() {
+    let point = Point { x: 1, y: 2 };
+    assert_eq!(format!("{:?}", point), "Point { x: 1, y: 2 }");
+    assert_eq!(format!("{:#?}", point), "Point { x: 1, y: 2 }");
+
+    assert_eq!(format!("{:?}", UserId(7)), "7");
+    assert_eq!(format!("{:?}", Mask { bits: 5 }), "0b0101");
+
+    let shape = Shape {
+        origin: point,
+        owner: UserId(7),
+        mask: Mask { bits: 5 },
+    };
+    let expected = r#"Shape {
+    origin: Point { x: 1, y: 2 },
+    owner: 7,
+    mask: 0b0101,
+}"#;
+    assert_eq!(format!("{:#?}", shape), expected);
+}
@@ -0,0 +1,51 @@
+// `#[debug(skip_if = "...")]` names a `fn(&T) -> bool` that is called on the
+// field every time the value is formatted. When it returns true the field is
+// left out of the output entirely, which keeps empty `Option`s and `Vec`s out
+// of the logs.
+//
+// Unlike `#[debug(skip)]` there is no trailing `..`, the field just isn't
+// there.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Request {
+    path: &'static str,
+    #[debug(skip_if = "Option::is_none")]
+    query: Option<&'static str>,
+    #[debug(skip_if = "Vec::is_empty")]
+    headers: Vec<&'static str>,
+    #[debug(skip_if = "is_zero")]
+    retries: u32,
+}
+
+#[derive(CustomDebug)]
+pub struct Pair(u8, #[debug(skip_if = "Option::is_none")] Option<u8>);
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
+fn main() {
+    let request = Request {
+        path: "/",
+        query: None,
+        headers: vec![],
+        retries: 0,
+    };
+    assert_eq!(format!("{:?}", request), r#"Request { path: "/" }"#);
+
+    let request = Request {
+        path: "/search",
+        query: Some("q=rust"),
+        headers: vec!["accept"],
+        retries: 2,
+    };
+    assert_eq!(
+        format!("{:?}", request),
+        r#"Request { path: "/search", query: Some("q=rust"), headers: ["accept"], retries: 2 }"#
+    );
+
+    assert_eq!(format!("{:?}", Pair(1, None)), "Pair(1)");
+    assert_eq!(format!("{:?}", Pair(1, Some(2))), "Pair(1, Some(2))");
+}
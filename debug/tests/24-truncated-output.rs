@@ -0,0 +1,56 @@
+// Large payloads make Debug output unreadable. `#[debug(max_len = N)]` cuts
+// strings after N chars and byte slices after N bytes, and
+// `#[debug(max_items = N)]` prints only the first N items of anything that
+// can be iterated by reference. Both end with how much was left out:
+//
+//     body: "GET / HTTP/1.1"… (12 more)
+//     ids: [1, 2, 3, … (7 more)]
+//
+// The items are always printed as a list, sets and maps included.
+
+#![deny(warnings)]
+
+use derive_debug::CustomDebug;
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+
+#[derive(CustomDebug)]
+pub struct Request<'a> {
+    #[debug(max_len = 4)]
+    path: String,
+    #[debug(max_len = 4)]
+    body: &'a [u8],
+    #[debug(max_len = 3)]
+    name: Cow<'a, str>,
+    #[debug(max_len = 8)]
+    short: &'a str,
+    #[debug(max_items = 3)]
+    ids: Vec<u32>,
+    #[debug(max_items = 2)]
+    tags: &'a BTreeSet<&'static str>,
+}
+
+#[derive(CustomDebug)]
+pub struct OnlyItems(#[debug(max_items = 1)] [u8; 4]);
+
+#[derive(CustomDebug)]
+pub struct OnlyLen(#[debug(max_len = 2)] Box<str>);
+
+fn main() {
+    let tags = ["a", "b", "c"].into_iter().collect();
+    let request = Request {
+        path: "/héllo/world".to_owned(),
+        body: &[1, 2, 3, 4, 5, 6],
+        name: Cow::Borrowed("ferris"),
+        short: "fits",
+        ids: (1..=10).collect(),
+        tags: &tags,
+    };
+    assert_eq!(
+        format!("{:?}", request),
+        r#"Request { path: "/hél"… (8 more), body: [1, 2, 3, 4, … (2 more)], name: "fer"… (3 more), short: "fits", ids: [1, 2, 3, … (7 more)], tags: ["a", "b", … (1 more)] }"#
+    );
+
+    assert_eq!(format!("{:?}", OnlyItems([9, 8, 7, 6])), "OnlyItems([9, … (3 more)])");
+    assert_eq!(format!("{:?}", OnlyLen("abc".into())), r#"OnlyLen("ab"… (1 more))"#);
+}
@@ -0,0 +1,69 @@
+// Fields of a `#[repr(packed)]` struct may be unaligned, so they can't be
+// borrowed the way the generated code normally does. The derive notices the
+// repr and copies each field into a local before formatting it, which means
+// every printed field has to be Copy, just like with the standard derive.
+//
+// A union doesn't know which of its fields holds a valid value, so it prints
+// as `Name { .. }`. `#[debug(with = "...")]` on the type itself hands the
+// whole value to a function instead, which also works for structs and enums.
+
+use derive_debug::CustomDebug;
+use std::fmt;
+
+#[derive(CustomDebug)]
+#[repr(C, packed)]
+pub struct Header {
+    tag: u8,
+    #[debug = "0x{:08x}"]
+    len: u32,
+    #[debug(skip_if = "Option::is_none")]
+    flags: Option<u16>,
+}
+
+#[derive(CustomDebug)]
+#[repr(packed(2))]
+pub struct Wrapped(u8, u64);
+
+#[derive(CustomDebug)]
+#[repr(packed)]
+#[debug(transparent)]
+pub struct Id(u64);
+
+#[derive(CustomDebug)]
+#[repr(C)]
+pub union Opaque {
+    int: u32,
+    float: f32,
+}
+
+#[derive(CustomDebug)]
+#[debug(with = "fmt_word")]
+pub union Word {
+    int: u32,
+    bytes: [u8; 4],
+}
+
+fn fmt_word(word: &Word, f: &mut fmt::Formatter) -> fmt::Result {
+    // every bit pattern is a valid u32
+    write!(f, "Word({:#x})", unsafe { word.int })
+}
+
+fn main() {
+    let header = Header {
+        tag: 1,
+        len: 42,
+        flags: None,
+    };
+    assert_eq!(format!("{:?}", header), "Header { tag: 1, len: 0x0000002a }");
+
+    assert_eq!(format!("{:?}", Wrapped(1, 2)), "Wrapped(1, 2)");
+    assert_eq!(format!("{:?}", Id(7)), "7");
+
+    let opaque = Opaque { int: 1 };
+    assert_eq!(format!("{:?}", opaque), "Opaque { .. }");
+    let _ = unsafe { opaque.float };
+
+    let word = Word { int: 0xff };
+    assert_eq!(format!("{:?}", word), "Word(0xff)");
+    let _ = unsafe { word.bytes };
+}
@@ -0,0 +1,83 @@
+// The generated code refers to everything through absolute `::core` paths, so
+// it keeps working when the caller redefines prelude names, has a module
+// called `std`, or has items that collide with the names the macro uses
+// internally. The Formatter argument of the generated `fmt` is hygienic, so a
+// function called `f` passed to `#[debug(with = "f")]` means that function.
+
+use derive_debug::{CustomDebug, CustomDisplay};
+
+mod std {}
+
+type Option = ();
+type Some = ();
+type None = ();
+type Result = ();
+type Ok = ();
+type Err = ();
+type Box = ();
+type String = ();
+type Vec = ();
+type Debug = ();
+type Display = ();
+
+pub struct Iterator;
+pub struct IntoIterator;
+
+fn f(value: &u8, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+    ::core::write!(formatter, "f({})", value)
+}
+
+fn is_zero(n: &u8) -> bool {
+    *n == 0
+}
+
+#[derive(CustomDebug, CustomDisplay)]
+#[display("{f}/{name}")]
+pub struct Field {
+    #[debug(with = "f")]
+    f: u8,
+    #[debug(max_len = 2)]
+    name: &'static str,
+    #[debug(max_items = 1)]
+    list: [u8; 2],
+    #[debug(skip_if = "is_zero")]
+    count: u8,
+    #[debug(redact)]
+    secret: u8,
+    #[debug = "{:>width$}"]
+    padded: u8,
+    #[debug(skip)]
+    width: usize,
+}
+
+#[derive(CustomDebug)]
+#[debug(compact)]
+pub enum Tree<T> {
+    Leaf(T),
+    Empty,
+}
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Id(u64);
+
+fn main() {
+    let field = Field {
+        f: 1,
+        name: "abc",
+        list: [2, 3],
+        count: 0,
+        secret: 4,
+        padded: 5,
+        width: 3,
+    };
+    assert_eq!(
+        format!("{:?}", field),
+        r#"Field { f: f(1), name: "ab"… (1 more), list: [2, … (1 more)], secret: ***, padded:   5, .. }"#
+    );
+    assert_eq!(format!("{}", field), "1/abc");
+
+    assert_eq!(format!("{:#?}", Tree::Leaf(1)), "Leaf(1)");
+    assert_eq!(format!("{:?}", Tree::<u8>::Empty), "Empty");
+    assert_eq!(format!("{:?}", Id(7)), "7");
+}
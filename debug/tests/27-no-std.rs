@@ -0,0 +1,42 @@
+// Nothing in the generated code needs `std`, so the derives work in `no_std`
+// crates. Here `std` is only linked for the test harness, under a name the
+// macro can't guess.
+
+#![no_std]
+
+extern crate alloc;
+extern crate std as _;
+
+use alloc::format;
+use derive_debug::{CustomDebug, CustomDisplay};
+
+#[derive(CustomDebug, CustomDisplay)]
+#[display("{name}")]
+pub struct Device<'a, T> {
+    name: &'a str,
+    #[debug(max_items = 2)]
+    registers: [T; 4],
+    #[debug(max_len = 3)]
+    firmware: &'a [u8],
+    #[debug(skip_if = "Option::is_none")]
+    irq: Option<u8>,
+}
+
+#[derive(CustomDebug)]
+#[debug(compact)]
+pub struct Pin(u8);
+
+fn main() {
+    let device = Device {
+        name: "uart0",
+        registers: [1u32, 2, 3, 4],
+        firmware: &[0xde, 0xad, 0xbe, 0xef],
+        irq: None,
+    };
+    assert_eq!(
+        format!("{:?}", device),
+        r#"Device { name: "uart0", registers: [1, 2, … (2 more)], firmware: [222, 173, 190, … (1 more)] }"#
+    );
+    assert_eq!(format!("{}", device), "uart0");
+    assert_eq!(format!("{:#?}", Pin(3)), "Pin(3)");
+}
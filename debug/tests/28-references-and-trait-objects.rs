@@ -0,0 +1,70 @@
+// Lifetime and const parameters are passed through to the impl untouched,
+// and a type parameter reached through a reference, an array or a slice gets
+// a bound like any other use of it. That includes `?Sized` parameters behind a
+// reference, which keep their `?Sized`.
+//
+// Fields that are Debug whatever their type parameters are add no bound:
+// trait objects, which need the trait itself to have Debug as a supertrait,
+// and raw pointers, which print their address.
+//
+//     impl<'a, 'b: 'a, T: ?Sized + Debug, U: Debug, P, const N: usize>
+//         Debug for Borrowed<'a, 'b, T, U, P, N>
+//     {...}
+
+use derive_debug::{CustomDebug, CustomDisplay};
+use std::fmt::{Debug, Display};
+
+pub trait Shape: Debug {
+    fn area(&self) -> u32;
+}
+
+#[derive(Debug)]
+pub struct Square(u32);
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+#[derive(CustomDebug)]
+pub struct Borrowed<'a, 'b: 'a, T: ?Sized, U, P, const N: usize> {
+    name: &'a T,
+    items: &'b mut [U],
+    shape: &'a dyn Shape,
+    boxed: Box<dyn Debug + 'a>,
+    bytes: [u8; N],
+    ptr: *const P,
+}
+
+#[derive(CustomDebug, CustomDisplay)]
+#[display("{0}")]
+pub struct Name<'a, T: ?Sized>(&'a T);
+
+fn assert_debug<T: Debug>() {}
+fn assert_display<T: Display>() {}
+
+fn main() {
+    struct NotDebug;
+
+    assert_debug::<Borrowed<str, u8, NotDebug, 4>>();
+    assert_debug::<Name<str>>();
+    assert_display::<Name<str>>();
+
+    let mut items = [1, 2];
+    let not_debug = NotDebug;
+    let borrowed = Borrowed {
+        name: "ferris",
+        items: &mut items,
+        shape: &Square(2),
+        boxed: Box::new(3),
+        bytes: [0; 2],
+        ptr: &not_debug as *const NotDebug,
+    };
+    let debug = format!("{:?}", borrowed);
+    let expected = r#"Borrowed { name: "ferris", items: [1, 2], shape: Square(2), boxed: 3, bytes: [0, 0], ptr: 0x"#;
+    assert!(debug.starts_with(expected));
+    assert_eq!(borrowed.shape.area(), 4);
+
+    assert_eq!(format!("{:?} {}", Name("x"), Name("x")), r#"Name("x") x"#);
+}
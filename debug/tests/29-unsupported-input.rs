@@ -0,0 +1,35 @@
+// Input the derives can't handle is rejected with an error pointing at the
+// offending part of the type.
+//
+// CustomDebug prints a union opaquely, but CustomDisplay and DebugFields have
+// no field to show. `#[debug(transparent)]` needs a struct with exactly one
+// field that is actually printed.
+
+use derive_debug::{CustomDebug, CustomDisplay};
+
+#[derive(CustomDisplay)]
+#[display("bits")]
+pub union Bits {
+    int: u32,
+    float: f32,
+}
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub enum Either {
+    Left(u8),
+    Right(u8),
+}
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Pair(u8, u8);
+
+#[derive(CustomDebug)]
+#[debug(transparent)]
+pub struct Hidden {
+    #[debug(skip)]
+    value: u8,
+}
+
+fn main() {}
@@ -0,0 +1,45 @@
+// Anything in a `#[debug]` attribute that the derive doesn't understand is an
+// error listing what is accepted in that position, on the struct, on a
+// variant, or on a field. The attribute also has to be either
+// `#[debug = "..."]` or `#[debug(...)]`, with string literals where a string
+// is expected.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(hidden)]
+pub struct UnknownOnStruct {
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub enum UnknownOnVariant {
+    #[debug(skip)]
+    Empty,
+}
+
+#[derive(CustomDebug)]
+pub struct UnknownOnField {
+    #[debug(hex)]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct PathForm {
+    #[debug]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct NotString {
+    #[debug = 8]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct NotInteger {
+    #[debug(max_len = "64")]
+    value: String,
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+// Bounds, `with` functions and `skip_if` predicates are given as strings and
+// parsed by the derive, so syntax errors inside them are reported on the
+// string literal.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(bound = "T: Debug U: Debug")]
+pub struct StructBound<T, U> {
+    value: T,
+    other: U,
+}
+
+#[derive(CustomDebug)]
+pub struct FieldBound<T> {
+    #[debug(bound = "T Debug")]
+    value: T,
+}
+
+#[derive(CustomDebug)]
+#[debug(bounds = "all")]
+pub struct Strategy<T> {
+    value: T,
+}
+
+#[derive(CustomDebug)]
+pub struct With {
+    #[debug(with = "fmt value")]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct SkipIf {
+    #[debug(skip_if = "Option::is_none()")]
+    value: Option<u8>,
+}
+
+fn main() {}
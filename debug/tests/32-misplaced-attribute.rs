@@ -0,0 +1,23 @@
+// Attributes that would silently do nothing are errors instead: the enum's
+// own name is never printed, tuple fields have no printed name, and
+// `redact_all_except` has to name fields that exist.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+#[debug(rename = "Choice")]
+pub enum Renamed {
+    A,
+}
+
+#[derive(CustomDebug)]
+pub struct Tuple(#[debug(rename = "first")] u8);
+
+#[derive(CustomDebug)]
+#[debug(redact_all_except(id, name))]
+pub struct User {
+    id: u32,
+    password: String,
+}
+
+fn main() {}
@@ -0,0 +1,52 @@
+// More mistakes in `#[debug = "..."]` format strings, next to the ones in
+// 16-bad-format-string.rs: arguments that don't exist, placeholders that
+// aren't valid Rust format syntax, raw identifiers, which format strings
+// spell without the `r#`, and `.*`, which would need an extra positional
+// argument.
+
+use derive_debug::CustomDebug;
+
+#[derive(CustomDebug)]
+pub struct Unmatched {
+    #[debug = "{}}"]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct SecondArgument {
+    #[debug = "{} {1}"]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct InvalidArgument {
+    #[debug = "{} {my-field}"]
+    value: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct RawArgument {
+    #[debug = "{} {r#type}"]
+    value: u8,
+    r#type: u8,
+}
+
+#[derive(CustomDebug)]
+pub struct StarPrecision {
+    #[debug = "{:.*}"]
+    value: f64,
+}
+
+#[derive(CustomDebug)]
+pub struct MissingPrecision {
+    #[debug = "{:.}"]
+    value: f64,
+}
+
+#[derive(CustomDebug)]
+pub struct NoPlaceholder {
+    #[debug = "value"]
+    value: u8,
+}
+
+fn main() {}
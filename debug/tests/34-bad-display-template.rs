@@ -0,0 +1,42 @@
+// CustomDisplay needs a template for the struct or for every variant, and the
+// template may only refer to fields that exist: by name for named fields, by
+// index for tuple fields.
+
+use derive_debug::CustomDisplay;
+
+#[derive(CustomDisplay)]
+pub struct Missing {
+    value: u8,
+}
+
+#[derive(CustomDisplay)]
+pub enum MissingOnVariant {
+    #[display("a")]
+    A,
+    B,
+}
+
+#[derive(CustomDisplay)]
+#[display("{}")]
+pub struct Positional {
+    value: u8,
+}
+
+#[derive(CustomDisplay)]
+#[display("{0} {2}")]
+pub struct OutOfRange(u8, u8);
+
+#[derive(CustomDisplay)]
+#[display("{name}")]
+pub struct UnknownField {
+    value: u8,
+}
+
+#[derive(CustomDisplay)]
+#[display("{value}")]
+pub struct OnField {
+    #[display("{}")]
+    value: u8,
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+// `fmt`, `redact`, `with`, `max_len` and `max_items` each decide how a field
+// is printed, so giving more than one of them on the same field is an error
+// instead of one of them silently winning. The error points at the option
+// that came second, across separate #[debug] attributes too.
+
+use derive_debug::CustomDebug;
+use std::fmt;
+
+fn fmt_hex(v: &Vec<u8>, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{:x?}", v)
+}
+
+#[derive(CustomDebug)]
+pub struct Payload {
+    #[debug(fmt = "{:?}x", max_items = 3)]
+    payload: Vec<u8>,
+}
+
+#[derive(CustomDebug)]
+pub struct Header {
+    #[debug(with = "fmt_hex", max_len = 4)]
+    header: Vec<u8>,
+}
+
+#[derive(CustomDebug)]
+pub struct Token {
+    #[debug = "{}!"]
+    #[debug(redact)]
+    token: String,
+}
+
+#[derive(CustomDebug)]
+pub struct Name {
+    #[debug(max_len = 4, max_len = 8)]
+    name: String,
+}
+
+fn main() {}
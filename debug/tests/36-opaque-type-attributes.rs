@@ -0,0 +1,53 @@
+// Types that are printed opaquely, unions and anything with a type-level
+// `#[debug(with = "...")]`, still have their field attributes checked even
+// though the fields are never printed. Type-level options that only affect
+// how fields are printed are errors there, since they would do nothing.
+
+use derive_debug::CustomDebug;
+use std::fmt;
+
+fn fmt_x<T>(_: &T, f: &mut fmt::Formatter) -> fmt::Result {
+    f.write_str("x")
+}
+
+#[derive(CustomDebug)]
+pub union Bits {
+    #[debug(nonsense = 1)]
+    int: u32,
+    float: f32,
+}
+
+#[derive(CustomDebug)]
+#[debug(with = "fmt_x")]
+pub struct X {
+    #[debug(bogus)]
+    a: u8,
+}
+
+#[derive(CustomDebug)]
+#[debug(with = "fmt_x", compact)]
+pub struct Compact {
+    a: u8,
+}
+
+#[derive(CustomDebug)]
+#[debug(redact_all_except(a))]
+#[debug(with = "fmt_x")]
+pub struct Redacted {
+    a: u8,
+}
+
+#[derive(CustomDebug)]
+#[debug(with = "fmt_x", bounds = "fields")]
+pub enum Bounded<T> {
+    A(T),
+}
+
+#[derive(CustomDebug)]
+#[debug(compact)]
+pub union Word {
+    int: u32,
+    bytes: [u8; 4],
+}
+
+fn main() {}
@@ -9,4 +9,32 @@ fn tests() {
     t.pass("tests/06-bound-trouble.rs");
     t.pass("tests/07-associated-type.rs");
     t.pass("tests/08-escape-hatch.rs");
+    t.pass("tests/09-enum.rs");
+    t.pass("tests/10-tuple-and-unit-struct.rs");
+    t.pass("tests/11-skip-and-redact.rs");
+    t.pass("tests/12-debug-with.rs");
+    t.pass("tests/13-rename.rs");
+    t.pass("tests/14-attribute-syntax.rs");
+    t.pass("tests/15-format-string.rs");
+    t.compile_fail("tests/16-bad-format-string.rs");
+    t.pass("tests/17-field-bound.rs");
+    t.pass("tests/18-nested-generics.rs");
+    t.pass("tests/19-field-bounds-strategy.rs");
+    t.pass("tests/20-qualified-associated-type.rs");
+    t.pass("tests/21-custom-display.rs");
+    t.pass("tests/22-compact-and-transparent.rs");
+    t.pass("tests/23-skip-if.rs");
+    t.pass("tests/24-truncated-output.rs");
+    t.pass("tests/25-packed-and-union.rs");
+    t.pass("tests/26-redefined-prelude-types.rs");
+    t.pass("tests/27-no-std.rs");
+    t.pass("tests/28-references-and-trait-objects.rs");
+    t.compile_fail("tests/29-unsupported-input.rs");
+    t.compile_fail("tests/30-unknown-attribute.rs");
+    t.compile_fail("tests/31-bad-bound.rs");
+    t.compile_fail("tests/32-misplaced-attribute.rs");
+    t.compile_fail("tests/33-bad-format-specifier.rs");
+    t.compile_fail("tests/34-bad-display-template.rs");
+    t.compile_fail("tests/35-conflicting-field-options.rs");
+    t.compile_fail("tests/36-opaque-type-attributes.rs");
 }
@@ -9,15 +9,22 @@ use crate::seq_parser::SeqParser;
 pub fn seq(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as SeqParser);
 
+    match do_expand(&ast) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn do_expand(ast: &SeqParser) -> syn::Result<proc_macro2::TokenStream> {
     let mut result = proc_macro2::TokenStream::new();
 
-    if let Some(expand) = ast.expand_repeat(&ast.body, ast.from, ast.to) {
+    if let Some(expand) = ast.expand_repeat(&ast.body)? {
         result.extend(expand);
     } else {
-        for i in ast.from..ast.to {
-            result.extend(ast.expand_normal(&ast.body, i));
+        for i in ast.n_iter() {
+            result.extend(ast.expand_normal(&ast.body, i)?);
         }
     }
 
-    result.into()
+    Ok(result)
 }
@@ -1,42 +1,88 @@
 use quote::quote;
 use syn::parse::Parse;
 
+// every value is a copy of the body, `0..=u16::MAX` is still allowed but a
+// typo like `0..=u64::MAX` shouldn't keep the compiler busy forever
+const MAX_COUNT: i128 = 1 << 16;
+
 pub(crate) struct SeqParser {
     pub(crate) n_ident: syn::Ident,
-    pub(crate) from: u32,
-    pub(crate) to: u32,
+    // The values of N are `from`, `from + step`, ... `count` of them, which
+    // is what any range with `.step_by(..)` and `.rev()` applied comes down
+    // to. They are kept as i128 so that anything from i64::MIN to u64::MAX
+    // fits without overflowing.
+    pub(crate) from: i128,
+    pub(crate) step: i128,
+    pub(crate) count: i128,
+    // `u8` when the range is written `0u8..10u8` or `0..=u8::MAX`, kept on
+    // every generated literal
+    pub(crate) suffix: String,
     pub(crate) body: proc_macro2::TokenStream,
 }
 
 impl Parse for SeqParser {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let mut has_equal = false;
         // N in 0..10
         // N in 0..=10
+        // N in -4..4
+        // N in 0..=u32::MAX
+        // N in 0..64 step 8
+        // N in (0..64).step_by(8)
+        // N in (0..8).rev()
         // N
         let n_ident: syn::Ident = input.parse()?;
 
         // in
         input.parse::<syn::Token![in]>()?;
 
-        // 0
-        let from: syn::LitInt = input.parse()?;
-        let from = from.base10_parse()?;
+        let range_span = input.span();
+        let mut range = if input.peek(syn::token::Paren) {
+            // (0..64)
+            let range_buf;
+            syn::parenthesized!(range_buf in input);
+            let mut range = parse_range(&range_buf)?;
 
-        // ..
-        input.parse::<syn::Token![..]>()?;
+            // .step_by(8).rev()
+            while input.parse::<Option<syn::Token![.]>>()?.is_some() {
+                let method: syn::Ident = input.parse()?;
+                let arg_buf;
+                syn::parenthesized!(arg_buf in input);
+                if method == "step_by" {
+                    range.step_by(&arg_buf.parse()?)?;
+                } else if method == "rev" {
+                    range.rev();
+                } else {
+                    return Err(syn::Error::new_spanned(
+                        method,
+                        "expected `step_by` or `rev`",
+                    ));
+                }
+                if !arg_buf.is_empty() {
+                    return Err(arg_buf.error("unexpected argument"));
+                }
+            }
+            range
+        } else {
+            parse_range(input)?
+        };
 
-        // =
-        if let Ok(_) = input.parse::<syn::Token![=]>() {
-            has_equal = true;
-            eprintln!("has_equal");
+        // 0..64 step 8
+        if input.peek(syn::Ident) {
+            let step: syn::Ident = input.parse()?;
+            if step != "step" {
+                return Err(syn::Error::new_spanned(step, "expected `step` or `{`"));
+            }
+            range.step_by(&input.parse()?)?;
         }
 
-        // 10
-        let to: syn::LitInt = input.parse()?;
-        let mut to = to.base10_parse()?;
-        if has_equal {
-            to += 1;
+        if range.count > MAX_COUNT {
+            return Err(syn::Error::new(
+                range_span,
+                format!(
+                    "the range has {} values, seq! expands at most {}",
+                    range.count, MAX_COUNT
+                ),
+            ));
         }
 
         let body_buf;
@@ -45,19 +91,142 @@ impl Parse for SeqParser {
 
         Ok(Self {
             n_ident,
-            from,
-            to,
+            from: range.from,
+            step: range.step,
+            count: range.count,
+            suffix: range.suffix,
             body,
         })
     }
 }
 
+struct Range {
+    from: i128,
+    step: i128,
+    count: i128,
+    suffix: String,
+}
+
+impl Range {
+    // the same values as Iterator::step_by
+    fn step_by(&mut self, step: &syn::LitInt) -> syn::Result<()> {
+        let n = step.base10_parse::<i128>()?;
+        if n <= 0 {
+            return Err(syn::Error::new_spanned(step, "step must be positive"));
+        }
+        self.step *= n;
+        self.count = (self.count + n - 1) / n;
+        Ok(())
+    }
+
+    // the same values as DoubleEndedIterator::rev
+    fn rev(&mut self) {
+        if self.count > 0 {
+            self.from += self.step * (self.count - 1);
+            self.step = -self.step;
+        }
+    }
+}
+
+// 0..10 or 0..=10
+fn parse_range(input: syn::parse::ParseStream) -> syn::Result<Range> {
+    // 0
+    let (from, from_suffix) = parse_bound(input)?;
+
+    // ..
+    input.parse::<syn::Token![..]>()?;
+
+    // =
+    let has_equal = input.parse::<Option<syn::Token![=]>>()?.is_some();
+
+    // 10
+    let to_span = input.span();
+    let (mut to, to_suffix) = parse_bound(input)?;
+    if has_equal {
+        to += 1;
+    }
+
+    // 0u8..10u16
+    if !from_suffix.is_empty() && !to_suffix.is_empty() && from_suffix != to_suffix {
+        return Err(syn::Error::new(
+            to_span,
+            format!(
+                "the range starts as `{}` but ends as `{}`, the bounds need the same type",
+                from_suffix, to_suffix
+            ),
+        ));
+    }
+
+    let suffix = if from_suffix.is_empty() {
+        to_suffix
+    } else {
+        from_suffix
+    };
+
+    Ok(Range {
+        from,
+        step: 1,
+        count: (to - from).max(0),
+        suffix,
+    })
+}
+
+// `4`, `-4`, `4u8`, `0x10` or `u32::MAX`, along with the literal's suffix, or
+// the type for a named bound
+fn parse_bound(input: syn::parse::ParseStream) -> syn::Result<(i128, String)> {
+    let is_negative = input.parse::<Option<syn::Token![-]>>()?.is_some();
+
+    let (value, suffix) = if input.peek(syn::LitInt) {
+        let lit: syn::LitInt = input.parse()?;
+        (lit.base10_parse::<i128>()?, lit.suffix().to_string())
+    } else {
+        // u32::MAX
+        let ty: syn::Ident = input.parse()?;
+        input.parse::<syn::Token![::]>()?;
+        let bound: syn::Ident = input.parse()?;
+        (get_int_bound(&ty, &bound)?, ty.to_string())
+    };
+
+    if is_negative {
+        Ok((-value, suffix))
+    } else {
+        Ok((value, suffix))
+    }
+}
+
+fn get_int_bound(ty: &syn::Ident, bound: &syn::Ident) -> syn::Result<i128> {
+    let (min, max) = match ty.to_string().as_str() {
+        "u8" => (u8::MIN as i128, u8::MAX as i128),
+        "u16" => (u16::MIN as i128, u16::MAX as i128),
+        "u32" => (u32::MIN as i128, u32::MAX as i128),
+        "u64" => (u64::MIN as i128, u64::MAX as i128),
+        "i8" => (i8::MIN as i128, i8::MAX as i128),
+        "i16" => (i16::MIN as i128, i16::MAX as i128),
+        "i32" => (i32::MIN as i128, i32::MAX as i128),
+        "i64" => (i64::MIN as i128, i64::MAX as i128),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "expected an integer literal or a bound like `u32::MAX`",
+            ))
+        }
+    };
+
+    if bound == "MIN" {
+        Ok(min)
+    } else if bound == "MAX" {
+        Ok(max)
+    } else {
+        Err(syn::Error::new_spanned(bound, "expected `MIN` or `MAX`"))
+    }
+}
+
 impl SeqParser {
     pub(crate) fn expand_normal(
         &self,
         ts: &proc_macro2::TokenStream,
-        n: u32,
-    ) -> proc_macro2::TokenStream {
+        n: i128,
+    ) -> syn::Result<proc_macro2::TokenStream> {
         let mut result = proc_macro2::TokenStream::new();
         let token_tree_arr = ts.clone().into_iter().collect::<Vec<_>>();
         let mut idx = 0;
@@ -65,7 +234,7 @@ impl SeqParser {
             let tree_node = &token_tree_arr[idx];
             match tree_node {
                 proc_macro2::TokenTree::Group(g) => {
-                    let inner_stream = self.expand_normal(&g.stream(), n);
+                    let inner_stream = self.expand_normal(&g.stream(), n)?;
 
                     let mut wrap_group = proc_macro2::Group::new(g.delimiter(), inner_stream);
                     wrap_group.set_span(g.span());
@@ -73,38 +242,47 @@ impl SeqParser {
                     idx += 1;
                 }
                 proc_macro2::TokenTree::Ident(ident) => {
-                    let mut is_find_target_n = false;
-                    if idx + 1 < token_tree_arr.len() {
-                        match token_tree_arr[idx + 1] {
-                            proc_macro2::TokenTree::Punct(ref punct) => {
-                                if punct.to_string() == "~" && idx + 2 < token_tree_arr.len() {
-                                    match token_tree_arr[idx + 2] {
-                                        proc_macro2::TokenTree::Ident(ref n_ident) => {
-                                            if n_ident == &self.n_ident {
-                                                let f_ident = syn::Ident::new(
-                                                    &format!("{}{}", ident.to_string(), n),
-                                                    ident.span(),
-                                                );
-
-                                                result.extend(quote!(#f_ident));
-
-                                                idx += 3;
-                                                is_find_target_n = true;
-                                            }
-                                        }
-                                        _ => {}
-                                    }
-                                }
-                            }
-                            _ => {}
-                        }
-                    }
+                    // Irq~N
+                    let is_find_target_n =
+                        match (token_tree_arr.get(idx + 1), token_tree_arr.get(idx + 2)) {
+                            (
+                                Some(proc_macro2::TokenTree::Punct(punct)),
+                                Some(proc_macro2::TokenTree::Ident(n_ident)),
+                            ) => punct.as_char() == '~' && n_ident == &self.n_ident,
+                            _ => false,
+                        };
 
-                    if !is_find_target_n {
+                    if is_find_target_n {
+                        if n < 0 {
+                            return Err(syn::Error::new_spanned(
+                                ident,
+                                format!(
+                                    "cannot paste the negative number {} into an identifier",
+                                    n
+                                ),
+                            ));
+                        }
+                        let f_ident = syn::Ident::new(&format!("{}{}", ident, n), ident.span());
+                        result.extend(quote!(#f_ident));
+                        idx += 3;
+                    } else {
                         if ident == &self.n_ident {
-                            let mut n_ident = proc_macro2::Literal::u32_unsuffixed(n);
-                            n_ident.set_span(ident.span());
-                            result.extend(quote!(#n_ident));
+                            // N.abs(), but not N..5, or -N
+                            let is_receiver = matches!(
+                                token_tree_arr.get(idx + 1),
+                                Some(proc_macro2::TokenTree::Punct(punct))
+                                    if punct.as_char() == '.'
+                                        && punct.spacing() == proc_macro2::Spacing::Alone
+                            );
+                            let is_negated = idx > 0
+                                && matches!(
+                                    token_tree_arr.get(idx - 1),
+                                    Some(proc_macro2::TokenTree::Punct(punct))
+                                        if punct.as_char() == '-'
+                                );
+                            let n_literal =
+                                self.to_literal(n, is_receiver || is_negated, ident.span());
+                            result.extend(quote!(#n_literal));
                         } else {
                             result.extend(quote!(#tree_node));
                         }
@@ -119,15 +297,18 @@ impl SeqParser {
             }
         }
 
-        result
+        Ok(result)
+    }
+
+    // every value of N, in order
+    pub(crate) fn n_iter(&self) -> impl Iterator<Item = i128> + '_ {
+        (0..self.count).map(move |i| self.from + self.step * i)
     }
 
     pub(crate) fn expand_repeat(
         &self,
         ts: &proc_macro2::TokenStream,
-        from: u32,
-        to: u32,
-    ) -> std::option::Option<proc_macro2::TokenStream> {
+    ) -> syn::Result<std::option::Option<proc_macro2::TokenStream>> {
         let mut result = proc_macro2::TokenStream::new();
         let mut is_found = false;
 
@@ -141,7 +322,7 @@ impl SeqParser {
                     //     Irq~N,
                     // )*
                     let mut is_found_inner = false;
-                    if punct.to_string() == "#" && idx + 1 < token_tree_arr.len() {
+                    if punct.as_char() == '#' && idx + 1 < token_tree_arr.len() {
                         if let proc_macro2::TokenTree::Group(ref group) = token_tree_arr[idx + 1] {
                             if group.delimiter() == proc_macro2::Delimiter::Parenthesis
                                 && idx + 2 < token_tree_arr.len()
@@ -149,13 +330,12 @@ impl SeqParser {
                                 if let proc_macro2::TokenTree::Punct(ref punct2) =
                                     token_tree_arr[idx + 2]
                                 {
-                                    if punct2.to_string() == "*" {
-                                        eprintln!("find");
+                                    if punct2.as_char() == '*' {
                                         // find
-                                        for i in from..to {
+                                        for i in self.n_iter() {
                                             let group_inner_stream = group.stream();
-                                            let expand = self.expand_normal(&group_inner_stream, i);
-                                            // eprintln!("extend: {:?}", expand);
+                                            let expand =
+                                                self.expand_normal(&group_inner_stream, i)?;
                                             result.extend(quote!(#expand));
                                         }
                                         is_found_inner = true;
@@ -173,7 +353,7 @@ impl SeqParser {
                 }
                 proc_macro2::TokenTree::Group(group) => {
                     let group_inner_stream = group.stream();
-                    if let Some(expand) = self.expand_repeat(&group_inner_stream, from, to) {
+                    if let Some(expand) = self.expand_repeat(&group_inner_stream)? {
                         let mut expand = proc_macro2::Group::new(group.delimiter(), expand);
                         expand.set_span(group.span());
                         result.extend(quote!(#expand));
@@ -192,12 +372,38 @@ impl SeqParser {
             }
         }
 
-        // eprintln!("result {:?}", result);
-
         if is_found {
-            Some(result)
+            Ok(Some(result))
         } else {
-            None
+            Ok(None)
+        }
+    }
+
+    // `n` written the way the range bounds were, `4` or `4u8`. A negative
+    // number followed by a method call or field access is wrapped in
+    // parentheses, `(-4).abs()`, so that the method is called on -4 instead
+    // of negating `4.abs()`, and so is one after a minus, `-(-4)`. Anywhere
+    // else it stays a literal, which is what a `$n:literal` macro argument or
+    // a range pattern expects.
+    fn to_literal(
+        &self,
+        n: i128,
+        is_parenthesized: bool,
+        span: proc_macro2::Span,
+    ) -> proc_macro2::TokenTree {
+        let mut literal: proc_macro2::Literal = format!("{}{}", n, self.suffix)
+            .parse()
+            .expect("an integer with a suffix taken from an integer literal");
+        literal.set_span(span);
+        if n >= 0 || !is_parenthesized {
+            return literal.into();
         }
+
+        let mut group = proc_macro2::Group::new(
+            proc_macro2::Delimiter::Parenthesis,
+            proc_macro2::TokenTree::from(literal).into(),
+        );
+        group.set_span(span);
+        group.into()
     }
 }
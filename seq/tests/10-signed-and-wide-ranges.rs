@@ -0,0 +1,92 @@
+// Bounds don't have to fit in a u32. They can be negative, go all the way up
+// to u64::MAX, and be written as `u32::MAX` or `i8::MIN` instead of a
+// literal. An inclusive range ending at the type's maximum works without
+// overflowing.
+//
+// When a bound is written with a suffix, like `0u8..4`, every generated
+// literal gets the same suffix, so the type of `N` is the one the caller
+// asked for. A named bound like `u32::MAX` gives its type the same way.
+//
+// The whole u64 range can be walked with a large enough step, but a range
+// of more than 65536 values is an error rather than an endless expansion.
+//
+// A negative N behaves like a parenthesized value, so `N.pow(2)` squares -2
+// instead of negating `2.pow(2)`, while it is still a literal for a macro
+// taking `$n:literal` and for range patterns.
+
+use seq::seq;
+
+macro_rules! lit {
+    ($n:literal) => {
+        $n
+    };
+}
+
+fn type_name_of<T>(_: T) -> &'static str {
+    std::any::type_name::<T>()
+}
+
+seq!(N in -4..4 {
+    const SIGNED: [i32; 8] = [#(N,)*];
+});
+
+seq!(N in 4294967293..=u32::MAX {
+    const TOP: [u32; 3] = [#(N,)*];
+});
+
+seq!(N in 18446744073709551614u64..=u64::MAX {
+    const WIDE: [u64; 2] = [#(N,)*];
+});
+
+seq!(N in (0..=u64::MAX).step_by(4611686018427387904) {
+    const QUARTERS: [u64; 4] = [#(N,)*];
+});
+
+seq!(N in i8::MIN..=-127i8 {
+    const BOTTOM: [i8; 2] = [#(N,)*];
+});
+
+fn main() {
+    assert_eq!(SIGNED, [-4, -3, -2, -1, 0, 1, 2, 3]);
+    assert_eq!(TOP, [u32::MAX - 2, u32::MAX - 1, u32::MAX]);
+    assert_eq!(WIDE, [u64::MAX - 1, u64::MAX]);
+    assert_eq!(BOTTOM, [-128, -127]);
+    assert_eq!(QUARTERS, [0, 1 << 62, 1 << 63, 3 << 62]);
+
+    let mut name_arr = vec![];
+    seq!(N in 0u8..2 {
+        name_arr.push(type_name_of(N));
+    });
+    seq!(N in -1..=0i16 {
+        name_arr.push(type_name_of(N));
+    });
+    seq!(N in 0..1 {
+        name_arr.push(type_name_of(N));
+    });
+    seq!(N in 4294967295..=u32::MAX {
+        name_arr.push(type_name_of(N));
+    });
+    assert_eq!(name_arr, ["u8", "u8", "i16", "i16", "i32", "u32"]);
+
+    let mut v = vec![];
+    seq!(N in -2i32..=-2i32 {
+        v.push(N.pow(2));
+        v.push(N.abs());
+        let n = N;
+        v.push(-N + n);
+    });
+    assert_eq!(v, [4, 2, 0]);
+
+    let mut lit_arr = vec![];
+    seq!(N in -2..0 {
+        lit_arr.push(lit!(N));
+    });
+    assert_eq!(lit_arr, [-2, -1]);
+
+    let mut in_range_arr = vec![];
+    seq!(N in -1..=-1 {
+        in_range_arr.push(matches!(-3, -4..=N));
+        in_range_arr.push(matches!(-3, N..=4));
+    });
+    assert_eq!(in_range_arr, [true, false]);
+}
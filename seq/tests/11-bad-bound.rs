@@ -0,0 +1,36 @@
+// A number can only be pasted into an identifier when it is not negative, and
+// named bounds have to be the MIN or MAX of a fixed-size integer type. A
+// range can have at most 65536 values, after any step is applied, and both
+// bounds have to be of the same type when both name one.
+
+use seq::seq;
+
+seq!(N in -2..2 {
+    fn f~N() {}
+});
+
+seq!(N in 0..usize::MAX {
+    fn g~N() {}
+});
+
+seq!(N in 0..u8::BITS {
+    fn h~N() {}
+});
+
+seq!(N in 0..=u64::MAX {
+    fn i~N() {}
+});
+
+seq!(N in (0..=u32::MAX).step_by(2) {
+    fn j~N() {}
+});
+
+seq!(N in 0u8..10u16 {
+    fn l~N() {}
+});
+
+seq!(N in 0i8..=u8::MAX {
+    fn m~N() {}
+});
+
+fn main() {}
@@ -0,0 +1,62 @@
+// The range can be strided and reversed, either with the iterator adapters on
+// a parenthesized range or with a trailing `step`:
+//
+//     seq!(N in (0..64).step_by(8) { ... })
+//     seq!(N in 0..64 step 8 { ... })
+//     seq!(N in (0..8).rev() { ... })
+//
+// The adapters are applied in the order they are written and produce the
+// same values as they would on the range at runtime, so `(0..8).step_by(3)
+// .rev()` is 6, 3, 0 while `(0..8).rev().step_by(3)` is 7, 4, 1.
+
+use seq::seq;
+
+seq!(N in (0..64).step_by(8) {
+    const STRIDED: [u32; 8] = [#(N,)*];
+});
+
+seq!(N in 0..=64 step 16 {
+    const STEPPED: [u32; 5] = [#(N,)*];
+});
+
+seq!(N in (0..8).rev() {
+    const REVERSED: [u32; 8] = [#(N,)*];
+});
+
+seq!(N in (0..8).step_by(3).rev() {
+    const STEP_THEN_REV: [u32; 3] = [#(N,)*];
+});
+
+seq!(N in (0..8).rev().step_by(3) {
+    const REV_THEN_STEP: [u32; 3] = [#(N,)*];
+});
+
+seq!(N in (0..0).rev() {
+    const EMPTY: [u32; 0] = [#(N,)*];
+});
+
+seq!(N in (0..32).step_by(8) {
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    enum Reg {
+        #(
+            Reg~N = N,
+        )*
+    }
+});
+
+fn main() {
+    assert_eq!(STRIDED, [0, 8, 16, 24, 32, 40, 48, 56]);
+    assert_eq!(STEPPED, [0, 16, 32, 48, 64]);
+    assert_eq!(REVERSED, [7, 6, 5, 4, 3, 2, 1, 0]);
+    assert_eq!(STEP_THEN_REV, [6, 3, 0]);
+    assert_eq!(REV_THEN_STEP, [7, 4, 1]);
+    assert_eq!(EMPTY, []);
+
+    assert_eq!(Reg::Reg24 as u32, 24);
+
+    let mut n_arr = vec![];
+    seq!(N in (-4..=4).rev().step_by(4) {
+        n_arr.push(N);
+    });
+    assert_eq!(n_arr, [4, 0, -4]);
+}
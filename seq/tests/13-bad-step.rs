@@ -0,0 +1,22 @@
+// The step has to be a positive integer, and only `step_by` and `rev` can be
+// called on the range.
+
+use seq::seq;
+
+seq!(N in 0..8 step 0 {
+    fn f~N() {}
+});
+
+seq!(N in (0..8).step_by(-2) {
+    fn g~N() {}
+});
+
+seq!(N in (0..8).skip(2) {
+    fn h~N() {}
+});
+
+seq!(N in (0..8).rev(1) {
+    fn i~N() {}
+});
+
+fn main() {}
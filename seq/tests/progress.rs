@@ -10,4 +10,8 @@ fn tests() {
     t.pass("tests/07-inclusive-range.rs");
     t.compile_fail("tests/08-ident-span.rs");
     t.pass("tests/09-interaction-with-macrorules.rs");
+    t.pass("tests/10-signed-and-wide-ranges.rs");
+    t.compile_fail("tests/11-bad-bound.rs");
+    t.pass("tests/12-step-and-rev.rs");
+    t.compile_fail("tests/13-bad-step.rs");
 }